@@ -0,0 +1,20 @@
+//! Encode and decode a code with the precomputed tables for a single ECC
+//! level. This is compared against `size_runtime_tables` and
+//! `size_builtin_all` to measure how much flash the "runtime-tables" feature
+//! saves:
+//!
+//! ```sh
+//! cargo build --release --examples --features runtime-tables
+//! ```
+use libhumancode::decoder::CHUNK_DECODER_4;
+use libhumancode::encoder::CHUNK_ENCODER_4;
+use std::hint::black_box;
+
+fn main() {
+    let data: [u8; 5] = black_box([0xde, 0xad, 0xbe, 0xef, 0x42]);
+    let code = CHUNK_ENCODER_4.encode_chunk(&data, 40).unwrap();
+    let (decoded, _) = CHUNK_DECODER_4
+        .decode_chunk(black_box(code.raw().as_str()), 40)
+        .unwrap();
+    assert_eq!(decoded.as_bytes(), &data);
+}
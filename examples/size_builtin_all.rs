@@ -0,0 +1,13 @@
+//! Encode and decode a code with `encode_chunk` and `decode_chunk`, with the
+//! ECC level chosen at runtime, so the precomputed tables for every level
+//! are kept. See `size_builtin`.
+use libhumancode::{decode_chunk, encode_chunk};
+use std::hint::black_box;
+
+fn main() {
+    let ecc = black_box(4);
+    let data: [u8; 5] = black_box([0xde, 0xad, 0xbe, 0xef, 0x42]);
+    let code = encode_chunk(&data, ecc, 40).unwrap();
+    let (decoded, _) = decode_chunk(black_box(code.raw().as_str()), ecc, 40).unwrap();
+    assert_eq!(decoded.as_bytes(), &data);
+}
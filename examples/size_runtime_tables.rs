@@ -0,0 +1,16 @@
+//! Encode and decode a code with tables that are computed at runtime. See
+//! `size_builtin`.
+use libhumancode::{RuntimeChunkEncoder, RS_TABLES_LEN};
+use std::hint::black_box;
+
+fn main() {
+    let mut tables = [0u8; RS_TABLES_LEN];
+    let encoder = RuntimeChunkEncoder::new(&mut tables, black_box(4)).unwrap();
+    let data: [u8; 5] = black_box([0xde, 0xad, 0xbe, 0xef, 0x42]);
+    let code = encoder.encode_chunk(&data, 40).unwrap();
+    let (decoded, _) = encoder
+        .decoder()
+        .decode_chunk(black_box(code.raw().as_str()), 40)
+        .unwrap();
+    assert_eq!(decoded.as_bytes(), &data);
+}
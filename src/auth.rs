@@ -0,0 +1,163 @@
+use crate::bits::{BitReader, BitWriter};
+use crate::decode_chunk::{decode_chunk, DecodedChunk};
+use crate::encode_chunk::{encode_chunk, EncodedChunk};
+use crate::error::{
+    authentication_failed, encode_buffer_doesnt_match_bits, invalid_bits, invalid_tag_bits,
+    HumancodeError,
+};
+use crate::smallbytebuf::SmallByteBuf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// A `CodeAuthenticator` creates codes that can't be forged without a secret key.
+///
+/// Error correcting symbols only protect against accidental mistakes - anyone
+/// can create a valid code for any data. A `CodeAuthenticator` appends a tag to
+/// the data before it's encoded: the first `tag_bits` bits of the
+/// HMAC-SHA256 of the data. When decoding, typos are corrected first and then
+/// the tag is checked, so a forged code is reported as an
+/// [`InputError`](crate::HumancodeError::InputError) with the
+/// [`AuthenticationFailed`](crate::InputErrorKind::AuthenticationFailed) kind.
+///
+/// The tag uses part of the 150 bit budget of a code, so `bits` plus
+/// `tag_bits` must be no more than 150. A guessed code is accepted with a
+/// probability of 1 in 2^`tag_bits`.
+#[derive(Clone)]
+pub struct CodeAuthenticator {
+    mac: Hmac<Sha256>,
+    tag_bits: u8,
+}
+
+impl CodeAuthenticator {
+    /// Create a new `CodeAuthenticator` from a secret key and the number of
+    /// bits to use for the tag - which must be in the range [1,149].
+    pub fn new(key: &[u8], tag_bits: u8) -> Result<CodeAuthenticator, HumancodeError> {
+        if tag_bits == 0 || tag_bits >= 150 {
+            return Err(invalid_tag_bits());
+        }
+        Ok(CodeAuthenticator {
+            mac: Hmac::new_from_slice(key).expect("HMAC accepts keys of any length"),
+            tag_bits,
+        })
+    }
+
+    /// Get the number of tag bits that are added to each code
+    pub fn tag_bits(&self) -> u8 {
+        self.tag_bits
+    }
+
+    /// Encode `data` along with its tag.
+    ///
+    /// `data`, `ecc`, and `bits` have the same meaning as for
+    /// [`encode_chunk`](crate::encode_chunk()) - `bits` is the number of
+    /// bits of `data`, not including the tag.
+    pub fn encode_chunk(
+        &self,
+        data: &[u8],
+        ecc: u8,
+        bits: u8,
+    ) -> Result<EncodedChunk, HumancodeError> {
+        let total_bits = self.total_bits(bits)?;
+        if data.len() != (bits as usize).div_ceil(8) {
+            return Err(encode_buffer_doesnt_match_bits());
+        }
+        // Like encode_chunk(), all unused bits of data must be 0
+        let unused_bits = data.len() * 8 - bits as usize;
+        if data[data.len() - 1] & ((1u16 << unused_bits) - 1) as u8 != 0 {
+            return Err(encode_buffer_doesnt_match_bits());
+        }
+
+        let mut writer = BitWriter::new();
+        writer.write_leading_bits(data, bits)?;
+        let tag = self.tag(data, bits);
+        writer.write_leading_bits(&tag, self.tag_bits)?;
+        encode_chunk(writer.as_bytes(), ecc, total_bits)
+    }
+
+    /// Decode and correct a code and then check its tag.
+    ///
+    /// `encoded_data`, `ecc`, and `bits` have the same meaning as for
+    /// [`decode_chunk`](crate::decode_chunk()) - `bits` is the number of
+    /// bits of data, not including the tag. The returned [`DecodedChunk`]
+    /// only contains the data.
+    ///
+    /// If the code can be decoded but its tag doesn't match, an
+    /// [`InputError`](crate::HumancodeError::InputError) with the
+    /// [`AuthenticationFailed`](crate::InputErrorKind::AuthenticationFailed)
+    /// kind is returned.
+    pub fn decode_chunk(
+        &self,
+        encoded_data: &str,
+        ecc: u8,
+        bits: u8,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        let total_bits = self.total_bits(bits)?;
+        let (decoded, corrected) = decode_chunk(encoded_data, ecc, total_bits)?;
+
+        let mut reader = BitReader::new(decoded.as_bytes(), total_bits)?;
+        let mut data = [0u8; 19];
+        let data_len = (bits as usize).div_ceil(8);
+        reader.read_leading_bits(&mut data[..data_len], bits)?;
+        let mut tag = [0u8; 19];
+        reader.read_leading_bits(&mut tag, self.tag_bits)?;
+
+        let mac = self.mac(&data[..data_len], bits);
+        let tag_len = (self.tag_bits as usize).div_ceil(8);
+        let partial_bits = self.tag_bits % 8;
+        if partial_bits != 0 {
+            // Only the leading bits of the last byte are part of the tag, so
+            // the rest are filled in from the HMAC to check whole bytes
+            let hash = mac.clone().finalize().into_bytes();
+            tag[tag_len - 1] |= hash[tag_len - 1] & (0xff >> partial_bits);
+        }
+        mac.verify_truncated_left(&tag[..tag_len])
+            .map_err(|_| authentication_failed())?;
+
+        Ok((
+            DecodedChunk::from_buf(SmallByteBuf::new(data, data_len as u8)),
+            corrected,
+        ))
+    }
+
+    fn total_bits(&self, bits: u8) -> Result<u8, HumancodeError> {
+        if bits == 0 || bits > 150 {
+            return Err(invalid_bits());
+        }
+        if bits as usize + self.tag_bits as usize > 150 {
+            return Err(invalid_tag_bits());
+        }
+        Ok(bits + self.tag_bits)
+    }
+
+    // Get the HMAC of `data`, which must have all unused bits set to 0
+    fn mac(&self, data: &[u8], bits: u8) -> Hmac<Sha256> {
+        let mut mac = self.mac.clone();
+        // Include the sizes so that tags for different layouts are unrelated
+        mac.update(&[bits, self.tag_bits]);
+        mac.update(data);
+        mac
+    }
+
+    // Calculate the tag for `data`, which must have all unused bits set to 0.
+    // Only the first `tag_bits` bits of the result are set.
+    fn tag(&self, data: &[u8], bits: u8) -> [u8; 19] {
+        let hash = self.mac(data, bits).finalize().into_bytes();
+
+        let mut tag = [0u8; 19];
+        let mut writer = BitWriter::new();
+        writer
+            .write_leading_bits(&hash, self.tag_bits)
+            .expect("write_leading_bits() failed - which shouldn't be possible since tag_bits is less than 150");
+        tag[..writer.as_bytes().len()].copy_from_slice(writer.as_bytes());
+        tag
+    }
+}
+
+impl core::fmt::Debug for CodeAuthenticator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Don't leak the key into logs
+        f.debug_struct("CodeAuthenticator")
+            .field("tag_bits", &self.tag_bits)
+            .finish_non_exhaustive()
+    }
+}
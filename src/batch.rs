@@ -0,0 +1,155 @@
+use crate::decode_chunk::DecodedChunk;
+use crate::encode_chunk::{ChunkFormat, EncodedChunk};
+use crate::error::{batch_infeasible, generate_attempts_exhausted, HumancodeError};
+use crate::generate::generate;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use rand_core::{CryptoRng, RngCore};
+
+// If this many random codes in a row are rejected, we give up. For any
+// batch that is reasonably far from the theoretical limits, this should
+// never happen.
+const MAX_CONSECUTIVE_REJECTIONS: usize = 10_000;
+
+/// Generate a batch of unique random codes that are all at least
+/// `min_distance` symbols apart from each other.
+///
+/// `ecc`, `bits` and `rng` have the same meaning as for
+/// [`generate`](crate::generate()).
+///
+/// The distance between two codes is the number of positions at which
+/// their symbols differ, including the error correcting symbols. Any two
+/// different codes are already at least `ecc + 1` symbols apart, so a
+/// larger `min_distance` is needed to make sure that a typo in one code
+/// can't be corrected into a different code from the same batch.
+///
+/// An error is returned if `count` codes can't possibly exist for the
+/// given parameters or if generation stops making progress.
+pub fn generate_batch<R: RngCore + CryptoRng + ?Sized>(
+    ecc: u8,
+    bits: u8,
+    count: usize,
+    min_distance: u8,
+    rng: &mut R,
+) -> Result<Vec<(DecodedChunk, EncodedChunk)>, HumancodeError> {
+    // Make sure the parameters are valid before doing anything else
+    let (_, first) = generate(ecc, bits, rng)?;
+    let code_len = first.encoded_len(ChunkFormat::Raw);
+
+    if !batch_is_feasible(bits, code_len, count, min_distance) {
+        return Err(batch_infeasible());
+    }
+
+    // Any two different codes are already at least `ecc + 1` symbols apart,
+    // so the index is only needed if a larger distance was requested.
+    let index_distance = if min_distance > ecc + 1 {
+        min_distance
+    } else {
+        0
+    };
+    let mut index = DistanceIndex::new(code_len, index_distance);
+    let mut seen = BTreeSet::new();
+    let mut result = Vec::with_capacity(count);
+    let mut rejections = 0;
+    while result.len() < count {
+        let (data, encoded) = generate(ecc, bits, rng)?;
+        if !seen.contains(&encoded) && !index.has_close_code(&encoded) {
+            seen.insert(encoded);
+            index.insert(encoded);
+            result.push((data, encoded));
+            rejections = 0;
+        } else {
+            rejections += 1;
+            if rejections >= MAX_CONSECUTIVE_REJECTIONS {
+                return Err(generate_attempts_exhausted());
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn batch_is_feasible(bits: u8, code_len: usize, count: usize, min_distance: u8) -> bool {
+    // There are only 2^bits different payloads
+    if bits < 64 && count as u64 > 1u64 << bits {
+        return false;
+    }
+    if min_distance as usize > code_len {
+        return false;
+    }
+    // Singleton bound - if any two codes differ in at least `min_distance`
+    // positions, then they must still differ after deleting `min_distance - 1`
+    // positions. So, there can be at most 32^(code_len - min_distance + 1) codes.
+    if min_distance > 1 {
+        let free_symbols = code_len - min_distance as usize + 1;
+        if free_symbols * 5 < 64 && count as u64 > 1u64 << (free_symbols * 5) {
+            return false;
+        }
+    }
+    true
+}
+
+// An index to quickly find codes within a given distance of a new code.
+//
+// If two codes differ in fewer than `min_distance` positions and we split
+// the positions into `min_distance` blocks, then the two codes must be
+// identical in at least one of the blocks. So, we only need to check the
+// distance to codes that share at least one block with the new code.
+struct DistanceIndex {
+    code_len: usize,
+    min_distance: usize,
+    blocks: BTreeMap<(usize, u128), Vec<EncodedChunk>>,
+}
+
+impl DistanceIndex {
+    fn new(code_len: usize, min_distance: u8) -> DistanceIndex {
+        DistanceIndex {
+            code_len,
+            min_distance: min_distance as usize,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    fn block_keys(&self, code: &EncodedChunk) -> impl Iterator<Item = (usize, u128)> {
+        let mut chars = [0u8; 31];
+        code.encode_into(ChunkFormat::Raw, &mut chars)
+            .expect("encode_into() failed - which shouldn't be possible since the buffer is large enough for any code");
+        let code_len = self.code_len;
+        let num_blocks = self.min_distance;
+        (0..num_blocks).map(move |block| {
+            // Since there are at least 2 blocks, each block is at most 16
+            // characters long and fits in a u128.
+            let start = block * code_len / num_blocks;
+            let end = (block + 1) * code_len / num_blocks;
+            let key = chars[start..end]
+                .iter()
+                .fold(0u128, |acc, &x| (acc << 8) | x as u128);
+            (block, key)
+        })
+    }
+
+    fn has_close_code(&self, code: &EncodedChunk) -> bool {
+        if self.min_distance <= 1 {
+            return false;
+        }
+        self.block_keys(code).any(|key| {
+            self.blocks.get(&key).is_some_and(|candidates| {
+                candidates.iter().any(|candidate| {
+                    let distance = code
+                        .distance(candidate)
+                        .expect("All codes in a batch have the same length");
+                    (distance as usize) < self.min_distance
+                })
+            })
+        })
+    }
+
+    fn insert(&mut self, code: EncodedChunk) {
+        if self.min_distance <= 1 {
+            return;
+        }
+        let keys: Vec<_> = self.block_keys(&code).collect();
+        for key in keys {
+            self.blocks.entry(key).or_default().push(code);
+        }
+    }
+}
@@ -0,0 +1,525 @@
+//! Command line tool to encode and decode human codes
+//!
+//! Run `humancode --help` for usage information.
+
+use libhumancode::{
+    confirm_correction, decode_chunk, encode_chunk, ChunkFormat, EncodedChunk, HighlightStyle,
+    HumancodeError,
+};
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, IsTerminal, Read, Write};
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage:
+    humancode encode --bits <BITS> --ecc <ECC> [--input <FORMAT>] [--pretty] [--batch] [DATA]
+    humancode decode --bits <BITS> --ecc <ECC> [--output <FORMAT>] [--pretty] [--batch] [--confirm] [CODE]
+
+Encode:
+    Encodes DATA as a code. If DATA isn't given, it is read from stdin.
+    --input <FORMAT>    Format of DATA: hex (default), base64, decimal, or raw.
+                        raw reads the bytes to encode from stdin.
+                        decimal is a number less than 2^BITS.
+    --pretty            Separate every 4 characters of the code with a \"-\"
+    --batch             Read one DATA value per line from stdin
+
+Decode:
+    Decodes and corrects CODE. If CODE isn't given, it is read from stdin.
+    Prints the data, the corrected code, and either \"ok\" or \"corrected\"
+    separated by tabs.
+    --output <FORMAT>   Format of the decoded data: hex (default), base64, or decimal
+    --pretty            Print the corrected code in the pretty format
+    --batch             Read one CODE per line from stdin. Lines that can't be
+                        decoded are reported with an \"error\" status.
+    --confirm           If the code had to be corrected, ask for confirmation
+                        before printing anything. Rejecting the correction is
+                        reported as an input error.
+
+Exit codes:
+    0   Success
+    1   Input error - a code couldn't be corrected or the data was invalid
+    2   Usage error - invalid arguments
+    3   I/O error
+";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum DataFormat {
+    Hex,
+    Base64,
+    Decimal,
+    Raw,
+}
+
+impl DataFormat {
+    fn parse(value: &str) -> Result<DataFormat, CliError> {
+        match value {
+            "hex" => Ok(DataFormat::Hex),
+            "base64" => Ok(DataFormat::Base64),
+            "decimal" => Ok(DataFormat::Decimal),
+            "raw" => Ok(DataFormat::Raw),
+            _ => Err(CliError::Usage(format!("Unknown data format: {}", value))),
+        }
+    }
+}
+
+enum CliError {
+    // Invalid command line arguments
+    Usage(String),
+    // Invalid data passed to encode
+    Input(String),
+    Humancode(HumancodeError),
+    Io(std::io::Error),
+}
+
+impl CliError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Input(_) | CliError::Humancode(HumancodeError::InputError(_)) => 1,
+            CliError::Usage(_) | CliError::Humancode(HumancodeError::UsageError(_)) => 2,
+            CliError::Io(_) => 3,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(msg) => write!(f, "{}", msg),
+            CliError::Input(msg) => write!(f, "Invalid data: {}", msg),
+            CliError::Humancode(err) => write!(f, "{}", err),
+            CliError::Io(err) => write!(f, "I/O Error: {}", err),
+        }
+    }
+}
+
+impl From<HumancodeError> for CliError {
+    fn from(err: HumancodeError) -> CliError {
+        CliError::Humancode(err)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(err: std::io::Error) -> CliError {
+        CliError::Io(err)
+    }
+}
+
+struct Options {
+    bits: u8,
+    ecc: u8,
+    format: DataFormat,
+    pretty: bool,
+    batch: bool,
+    confirm: bool,
+    value: Option<String>,
+}
+
+fn parse_options(args: &[String], format_flag: &str) -> Result<Options, CliError> {
+    fn parse_number(flag: &str, value: Option<&String>) -> Result<u8, CliError> {
+        let value = value.ok_or_else(|| CliError::Usage(format!("{} requires a value", flag)))?;
+        value
+            .parse()
+            .map_err(|_| CliError::Usage(format!("Invalid value for {}: {}", flag, value)))
+    }
+
+    let mut bits = None;
+    let mut ecc = None;
+    let mut format = DataFormat::Hex;
+    let mut pretty = false;
+    let mut batch = false;
+    let mut confirm = false;
+    let mut value = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--bits" => bits = Some(parse_number(arg, iter.next())?),
+            "--ecc" => ecc = Some(parse_number(arg, iter.next())?),
+            "--pretty" => pretty = true,
+            "--batch" => batch = true,
+            "--confirm" if format_flag == "--output" => confirm = true,
+            flag if flag == format_flag => {
+                let v = iter
+                    .next()
+                    .ok_or_else(|| CliError::Usage(format!("{} requires a value", flag)))?;
+                format = DataFormat::parse(v)?;
+            }
+            flag if flag.starts_with("--") => {
+                return Err(CliError::Usage(format!("Unknown option: {}", flag)))
+            }
+            _ if value.is_none() => value = Some(arg.clone()),
+            _ => return Err(CliError::Usage(format!("Unexpected argument: {}", arg))),
+        }
+    }
+
+    let bits = bits.ok_or_else(|| CliError::Usage("--bits is required".to_string()))?;
+    let ecc = ecc.ok_or_else(|| CliError::Usage("--ecc is required".to_string()))?;
+    if batch && value.is_some() {
+        return Err(CliError::Usage(
+            "A value can't be given on the command line in batch mode".to_string(),
+        ));
+    }
+    if batch && confirm {
+        return Err(CliError::Usage(
+            "--confirm can't be used in batch mode".to_string(),
+        ));
+    }
+
+    Ok(Options {
+        bits,
+        ecc,
+        format,
+        pretty,
+        batch,
+        confirm,
+        value,
+    })
+}
+
+fn chunk_format(pretty: bool) -> ChunkFormat {
+    if pretty {
+        ChunkFormat::Pretty
+    } else {
+        ChunkFormat::Raw
+    }
+}
+
+fn read_stdin_line() -> Result<String, CliError> {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+fn encode(args: &[String]) -> Result<(), CliError> {
+    let options = parse_options(args, "--input")?;
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    let encode_one = |data: &[u8], out: &mut dyn Write| -> Result<(), CliError> {
+        let encoded = encode_chunk(data, options.ecc, options.bits)?;
+        encoded.write_to_io(chunk_format(options.pretty), out)?;
+        writeln!(out)?;
+        Ok(())
+    };
+
+    if options.format == DataFormat::Raw {
+        if options.batch || options.value.is_some() {
+            return Err(CliError::Usage(
+                "raw input is always read from stdin and can't be used in batch mode".to_string(),
+            ));
+        }
+        let mut data = Vec::new();
+        std::io::stdin().lock().read_to_end(&mut data)?;
+        return encode_one(&data, &mut out);
+    }
+
+    if options.batch {
+        let stdin = std::io::stdin();
+        for line in stdin.lock().lines() {
+            let data = parse_data(line?.trim(), options.format, options.bits)?;
+            encode_one(&data, &mut out)?;
+        }
+        return Ok(());
+    }
+
+    let value = match &options.value {
+        Some(value) => value.clone(),
+        None => read_stdin_line()?,
+    };
+    let data = parse_data(&value, options.format, options.bits)?;
+    encode_one(&data, &mut out)
+}
+
+fn decode(args: &[String]) -> Result<(), CliError> {
+    let options = parse_options(args, "--output")?;
+    if options.format == DataFormat::Raw {
+        return Err(CliError::Usage(
+            "raw isn't a supported output format".to_string(),
+        ));
+    }
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    let decode_one = |code: &str, out: &mut dyn Write| -> Result<(), CliError> {
+        let (decoded, corrected) = decode_chunk(code, options.ecc, options.bits)?;
+        let (chunk, status) = match corrected {
+            Some(chunk) if options.confirm => {
+                // Prompt on stderr so that stdout only ever contains results
+                let stderr = std::io::stderr();
+                let style = if stderr.is_terminal() {
+                    HighlightStyle::Ansi
+                } else {
+                    HighlightStyle::Markers
+                };
+                let accepted = confirm_correction(
+                    code,
+                    &chunk,
+                    style,
+                    &mut std::io::stdin().lock(),
+                    &mut stderr.lock(),
+                )?;
+                if !accepted {
+                    return Err(CliError::Input("the correction was rejected".to_string()));
+                }
+                (chunk, "corrected")
+            }
+            Some(chunk) => (chunk, "corrected"),
+            None => (code.parse::<EncodedChunk>()?, "ok"),
+        };
+        write!(
+            out,
+            "{}\t",
+            format_data(decoded.as_bytes(), options.format, options.bits)
+        )?;
+        chunk.write_to_io(chunk_format(options.pretty), out)?;
+        writeln!(out, "\t{}", status)?;
+        Ok(())
+    };
+
+    if options.batch {
+        // In batch mode, we keep going after errors so that every line gets a
+        // result. The exit code reflects the most severe error that was seen.
+        let mut worst_error: Option<CliError> = None;
+        let stdin = std::io::stdin();
+        for line in stdin.lock().lines() {
+            let line = line?;
+            if let Err(err) = decode_one(line.trim(), &mut out) {
+                if let CliError::Io(_) = err {
+                    return Err(err);
+                }
+                writeln!(out, "\t\terror: {}", err)?;
+                if worst_error
+                    .as_ref()
+                    .is_none_or(|e| e.exit_code() < err.exit_code())
+                {
+                    worst_error = Some(err);
+                }
+            }
+        }
+        return match worst_error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        };
+    }
+
+    let code = match &options.value {
+        Some(value) => value.clone(),
+        None => read_stdin_line()?,
+    };
+    decode_one(&code, &mut out)
+}
+
+fn parse_data(value: &str, format: DataFormat, bits: u8) -> Result<Vec<u8>, CliError> {
+    match format {
+        DataFormat::Hex => parse_hex(value),
+        DataFormat::Base64 => parse_base64(value),
+        DataFormat::Decimal => parse_decimal(value, bits),
+        DataFormat::Raw => Ok(value.as_bytes().to_vec()),
+    }
+}
+
+fn format_data(data: &[u8], format: DataFormat, bits: u8) -> String {
+    match format {
+        DataFormat::Hex => data.iter().map(|x| format!("{:02x}", x)).collect(),
+        DataFormat::Base64 => format_base64(data),
+        DataFormat::Decimal => format_decimal(data, bits),
+        DataFormat::Raw => unreachable!("raw output is rejected when parsing arguments"),
+    }
+}
+
+fn parse_hex(value: &str) -> Result<Vec<u8>, CliError> {
+    if value.len() % 2 != 0 || !value.is_ascii() {
+        return Err(CliError::Input(
+            "hex data must have an even number of digits".to_string(),
+        ));
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&value[i..i + 2], 16)
+                .map_err(|_| CliError::Input(format!("invalid hex data: {}", value)))
+        })
+        .collect()
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn parse_base64(value: &str) -> Result<Vec<u8>, CliError> {
+    let invalid = || CliError::Input(format!("invalid base64 data: {}", value));
+    let value = value.trim_end_matches('=');
+    let mut out = Vec::new();
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    for c in value.bytes() {
+        let v = BASE64_ALPHABET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or_else(invalid)?;
+        acc = (acc << 6) | v as u32;
+        acc_bits += 6;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            out.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    // Any bits left over must be padding bits which are always 0
+    if acc != 0 || acc_bits >= 6 {
+        return Err(invalid());
+    }
+    Ok(out)
+}
+
+fn format_base64(data: &[u8]) -> String {
+    let mut out = String::new();
+    for group in data.chunks(3) {
+        let mut acc = 0u32;
+        for (i, &x) in group.iter().enumerate() {
+            acc |= (x as u32) << (16 - 8 * i);
+        }
+        for i in 0..4 {
+            if i <= group.len() {
+                out.push(BASE64_ALPHABET[((acc >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+// Decimal values are treated as `bits`-bit numbers. Since encode_chunk()
+// encodes the _highest_ bits of its input, the number is shifted so that
+// its lowest bit lines up with the last encoded bit.
+fn parse_decimal(value: &str, bits: u8) -> Result<Vec<u8>, CliError> {
+    if bits == 0 || bits > 150 {
+        return Err(CliError::Usage(
+            "--bits must be in the range [1,150]".to_string(),
+        ));
+    }
+    let len = (bits as usize).div_ceil(8);
+    let shift = len * 8 - bits as usize;
+    if value.is_empty() {
+        return Err(CliError::Input(
+            "decimal data must not be empty".to_string(),
+        ));
+    }
+    // Big-endian accumulator with 1 extra byte so overflow can be detected
+    let mut acc = vec![0u8; len + 1];
+    for c in value.bytes() {
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            _ => return Err(CliError::Input(format!("invalid decimal data: {}", value))),
+        };
+        let mut carry = digit as u32;
+        for x in acc.iter_mut().rev() {
+            let v = *x as u32 * 10 + carry;
+            *x = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 || acc[0] != 0 {
+            return Err(CliError::Input(format!(
+                "decimal data is too large for {} bits: {}",
+                bits, value
+            )));
+        }
+    }
+    // Make sure that the value fits in `bits` bits and then shift it into place
+    if shift > 0 && acc[1] >> (8 - shift) != 0 {
+        return Err(CliError::Input(format!(
+            "decimal data is too large for {} bits: {}",
+            bits, value
+        )));
+    }
+    let mut out = vec![0u8; len];
+    for i in 0..len {
+        let hi = (acc[i + 1] as u16) << shift;
+        let lo = if i + 2 < acc.len() && shift > 0 {
+            (acc[i + 2] >> (8 - shift)) as u16
+        } else {
+            0
+        };
+        out[i] = (hi | lo) as u8;
+    }
+    Ok(out)
+}
+
+fn format_decimal(data: &[u8], bits: u8) -> String {
+    let shift = data.len() * 8 - bits as usize;
+    // Undo the shift from parse_decimal()
+    let mut value = vec![0u8; data.len()];
+    for i in 0..data.len() {
+        let hi = if i > 0 && shift > 0 {
+            data[i - 1] << (8 - shift)
+        } else {
+            0
+        };
+        value[i] = hi | (data[i] >> shift);
+    }
+    let mut digits = Vec::new();
+    while value.iter().any(|&x| x != 0) {
+        let mut rem = 0u32;
+        for x in value.iter_mut() {
+            let v = (rem << 8) | *x as u32;
+            *x = (v / 10) as u8;
+            rem = v % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    digits.iter().rev().map(|&x| x as char).collect()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(|x| x.as_str()) {
+        Some("encode") => encode(&args[1..]),
+        Some("decode") => decode(&args[1..]),
+        Some("--help") | Some("-h") | Some("help") => {
+            print!("{}", USAGE);
+            return;
+        }
+        _ => Err(CliError::Usage(format!(
+            "Missing or unknown command\n\n{}",
+            USAGE
+        ))),
+    };
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        exit(err.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_formats() {
+        assert_eq!(parse_hex("00ff10").ok(), Some(vec![0, 255, 16]));
+        assert!(parse_hex("0").is_err());
+        assert!(parse_hex("zz").is_err());
+
+        for data in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+            let encoded = format_base64(data);
+            assert_eq!(parse_base64(&encoded).ok().as_deref(), Some(data));
+        }
+        assert_eq!(format_base64(b"foob"), "Zm9vYg==");
+        assert!(parse_base64("Zm9vYh==").is_err());
+
+        assert_eq!(parse_decimal("5", 3).ok(), Some(vec![0b1010_0000]));
+        assert_eq!(parse_decimal("65535", 16).ok(), Some(vec![255, 255]));
+        assert!(parse_decimal("8", 3).is_err());
+        assert!(parse_decimal("65536", 16).is_err());
+        assert_eq!(format_decimal(&[0b1010_0000], 3), "5");
+
+        let big = "1427247692705959881058285969449495136382746623";
+        let data = parse_decimal(big, 150).ok().unwrap();
+        assert_eq!(data.len(), 19);
+        assert_eq!(format_decimal(&data, 150), big);
+        assert!(parse_decimal("1427247692705959881058285969449495136382746624", 150).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::error::{
+    bit_buffer_doesnt_match_bits, bit_field_too_wide, bit_field_value_too_big,
+    bit_reader_exhausted, bit_writer_full, invalid_bits, HumancodeError,
+};
+
+/// A `BitWriter` packs fields of arbitrary bit widths into a buffer
+/// suitable for passing to [`encode_chunk`](crate::encode_chunk()).
+///
+/// Fields are written in a big-endian fashion, starting from the highest
+/// bit of the first byte. Any unused bits in the final byte are always 0,
+/// so [`as_bytes`](BitWriter::as_bytes) and [`bits`](BitWriter::bits) can
+/// be passed directly to `encode_chunk`.
+///
+/// A `BitWriter` can hold at most 150 bits.
+#[derive(Copy, Clone, Debug)]
+pub struct BitWriter {
+    buf: [u8; 19],
+    bits: u8,
+}
+
+impl BitWriter {
+    /// Create a new, empty, `BitWriter`
+    pub const fn new() -> BitWriter {
+        BitWriter {
+            buf: [0u8; 19],
+            bits: 0,
+        }
+    }
+
+    /// Append the lowest `width` bits of `value`.
+    ///
+    /// `width` must be no larger than 64 and `value` must fit in `width`
+    /// bits. Nothing is written if an error is returned.
+    pub fn write(&mut self, value: u64, width: u8) -> Result<(), HumancodeError> {
+        if width > 64 {
+            return Err(bit_field_too_wide());
+        }
+        if width < 64 && value >> width != 0 {
+            return Err(bit_field_value_too_big());
+        }
+        if self.bits as usize + width as usize > 150 {
+            return Err(bit_writer_full());
+        }
+        for i in (0..width).rev() {
+            let bit = ((value >> i) & 1) as u8;
+            self.buf[self.bits as usize / 8] |= bit << (7 - self.bits % 8);
+            self.bits += 1;
+        }
+        Ok(())
+    }
+
+    /// Append a single bit
+    pub fn write_bool(&mut self, value: bool) -> Result<(), HumancodeError> {
+        self.write(value as u64, 1)
+    }
+
+    /// Append all 8 bits of every byte in `bytes`.
+    ///
+    /// Nothing is written if an error is returned.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), HumancodeError> {
+        if self.bits as usize + bytes.len() * 8 > 150 {
+            return Err(bit_writer_full());
+        }
+        for &b in bytes.iter() {
+            self.write(b as u64, 8)?;
+        }
+        Ok(())
+    }
+
+    // Append the first `bits` bits of `bytes`
+    pub(crate) fn write_leading_bits(
+        &mut self,
+        bytes: &[u8],
+        bits: u8,
+    ) -> Result<(), HumancodeError> {
+        if self.bits as usize + bits as usize > 150 {
+            return Err(bit_writer_full());
+        }
+        for i in 0..bits as usize {
+            self.write_bool((bytes[i / 8] >> (7 - i % 8)) & 1 == 1)?;
+        }
+        Ok(())
+    }
+
+    /// Get the number of bits that have been written so far
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Get the written data. The length will be the minimal number of bytes
+    /// required to hold [`bits`](BitWriter::bits) bits.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[0..(self.bits as usize).div_ceil(8)]
+    }
+}
+
+impl Default for BitWriter {
+    fn default() -> BitWriter {
+        BitWriter::new()
+    }
+}
+
+/// A `BitReader` reads fields of arbitrary bit widths back out of data
+/// produced by a [`BitWriter`] - for example, the bytes of a
+/// [`DecodedChunk`](crate::DecodedChunk).
+#[derive(Copy, Clone, Debug)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    bits: u8,
+    pos: u8,
+}
+
+impl<'a> BitReader<'a> {
+    /// Create a new `BitReader`.
+    ///
+    /// `bits` is the total number of bits available in `data` and should
+    /// match the value passed to [`decode_chunk`](crate::decode_chunk()).
+    /// It must be at least 1 and less than or equal to 150 and `data` must
+    /// have the minimal length given the number of `bits`.
+    pub fn new(data: &'a [u8], bits: u8) -> Result<BitReader<'a>, HumancodeError> {
+        if bits == 0 || bits > 150 {
+            return Err(invalid_bits());
+        }
+        if data.len() != (bits as usize).div_ceil(8) {
+            return Err(bit_buffer_doesnt_match_bits());
+        }
+        Ok(BitReader { data, bits, pos: 0 })
+    }
+
+    /// Read the next `width` bits as an integer.
+    ///
+    /// `width` must be no larger than 64. Nothing is consumed if an
+    /// error is returned.
+    pub fn read(&mut self, width: u8) -> Result<u64, HumancodeError> {
+        if width > 64 {
+            return Err(bit_field_too_wide());
+        }
+        if width > self.remaining() {
+            return Err(bit_reader_exhausted());
+        }
+        let mut value = 0u64;
+        for _ in 0..width {
+            let bit = (self.data[self.pos as usize / 8] >> (7 - self.pos % 8)) & 1;
+            value = (value << 1) | bit as u64;
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    /// Read the next bit
+    pub fn read_bool(&mut self) -> Result<bool, HumancodeError> {
+        Ok(self.read(1)? == 1)
+    }
+
+    /// Fill `out` with the next `out.len() * 8` bits.
+    ///
+    /// Nothing is consumed if an error is returned.
+    pub fn read_bytes(&mut self, out: &mut [u8]) -> Result<(), HumancodeError> {
+        if out.len() * 8 > self.remaining() as usize {
+            return Err(bit_reader_exhausted());
+        }
+        for x in out.iter_mut() {
+            *x = self.read(8)? as u8;
+        }
+        Ok(())
+    }
+
+    // Read the next `bits` bits into the start of `out`, zeroing the rest of `out`
+    pub(crate) fn read_leading_bits(
+        &mut self,
+        out: &mut [u8],
+        bits: u8,
+    ) -> Result<(), HumancodeError> {
+        if bits > self.remaining() {
+            return Err(bit_reader_exhausted());
+        }
+        for x in out.iter_mut() {
+            *x = 0;
+        }
+        for i in 0..bits as usize {
+            out[i / 8] |= (self.read(1)? as u8) << (7 - i % 8);
+        }
+        Ok(())
+    }
+
+    /// Get the number of bits that haven't been read yet
+    pub fn remaining(&self) -> u8 {
+        self.bits - self.pos
+    }
+}
+
+// Get a value with the lowest `width` bits set
+pub(crate) fn mask(width: u8) -> u128 {
+    if width >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << width) - 1
+    }
+}
@@ -0,0 +1,93 @@
+use crate::encode_chunk::{ChunkFormat, EncodedChunk};
+#[cfg(feature = "rand")]
+use crate::{
+    decode_chunk::DecodedChunk,
+    error::{generate_attempts_exhausted, HumancodeError},
+    generate::generate,
+};
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+/// A default list of English words that shouldn't appear in generated codes.
+///
+/// z-base-32 doesn't use the letters "l" or "v", so words containing them
+/// can never appear in a code and aren't included.
+pub const DEFAULT_BLOCKLIST: Blocklist<'static> = Blocklist::new(&[
+    "anus", "arse", "ass", "bastard", "bitch", "boob", "butt", "chink", "cock", "coon", "crap",
+    "cum", "cunt", "damn", "dick", "dyke", "fag", "fuck", "gook", "homo", "jizz", "kike", "kkk",
+    "nazi", "nigg", "nude", "penis", "piss", "poop", "porn", "prick", "pussy", "rape", "semen",
+    "sex", "shit", "spic", "suck", "tit", "turd", "twat", "wank", "whore",
+]);
+
+/// A `Blocklist` checks codes for substrings that shouldn't appear in them -
+/// for example, offensive words that could appear by chance in a random code.
+///
+/// Matching ignores ASCII case.
+#[derive(Copy, Clone, Debug)]
+pub struct Blocklist<'a> {
+    words: &'a [&'a str],
+}
+
+impl<'a> Blocklist<'a> {
+    /// Create a new `Blocklist` from a list of substrings
+    pub const fn new(words: &'a [&'a str]) -> Blocklist<'a> {
+        Blocklist { words }
+    }
+
+    /// Find the first blocked substring that appears in `code`.
+    ///
+    /// Both the "raw" and the "pretty" format of the code are checked. Since
+    /// the "raw" format has no separators, words that cross a group boundary
+    /// in the "pretty" format are found as well.
+    pub fn find(&self, code: &EncodedChunk) -> Option<&'a str> {
+        let mut raw = [0u8; 38];
+        let raw_len = code
+            .encode_into(ChunkFormat::Raw, &mut raw)
+            .expect("encode_into() failed - which shouldn't be possible since the buffer is large enough for any code");
+        let mut pretty = [0u8; 38];
+        let pretty_len = code
+            .encode_into(ChunkFormat::Pretty, &mut pretty)
+            .expect("encode_into() failed - which shouldn't be possible since the buffer is large enough for any code");
+
+        self.words.iter().copied().find(|word| {
+            contains_ignore_case(&raw[..raw_len], word.as_bytes())
+                || contains_ignore_case(&pretty[..pretty_len], word.as_bytes())
+        })
+    }
+
+    /// Check if any blocked substring appears in `code`
+    pub fn is_blocked(&self, code: &EncodedChunk) -> bool {
+        self.find(code).is_some()
+    }
+
+    /// Generate a random code that doesn't contain any blocked substrings
+    ///
+    /// `ecc`, `bits` and `rng` have the same meaning as for
+    /// [`generate`](crate::generate()). Random codes are generated until one is
+    /// found that isn't blocked, or until `max_attempts` codes have been tried.
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        ecc: u8,
+        bits: u8,
+        max_attempts: u32,
+        rng: &mut R,
+    ) -> Result<(DecodedChunk, EncodedChunk), HumancodeError> {
+        for _ in 0..max_attempts {
+            let (data, encoded) = generate(ecc, bits, rng)?;
+            if !self.is_blocked(&encoded) {
+                return Ok((data, encoded));
+            }
+        }
+        Err(generate_attempts_exhausted())
+    }
+}
+
+fn contains_ignore_case(haystack: &[u8], needle: &[u8]) -> bool {
+    if needle.is_empty() {
+        return true;
+    }
+    haystack
+        .windows(needle.len())
+        .any(|window| window.eq_ignore_ascii_case(needle))
+}
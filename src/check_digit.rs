@@ -0,0 +1,144 @@
+use crate::decode_chunk::{DecodedChunk, CHUNK_DECODER_0};
+use crate::encode_chunk::{EncodedChunk, CHUNK_ENCODER_0};
+use crate::error::{
+    check_failed, decode_buffer_smaller_than_ecc, decode_buffer_too_big, invalid_character,
+    HumancodeError,
+};
+use libzbase32::low_level_decode::character_to_quintet;
+
+// An antisymmetric permutation of the dihedral group D16, where element k is
+// the rotation k % 16 followed by a reflection if k >= 16. For any x != y,
+// x * SIGMA[y] != y * SIGMA[x], which is what lets Verhoeff's scheme detect
+// every adjacent transposition.
+const VERHOEFF_SIGMA: [u8; 32] = [
+    18, 3, 7, 29, 25, 6, 22, 30, 17, 0, 9, 23, 4, 26, 20, 10, 31, 27, 19, 8, 28, 13, 21, 1, 14, 11,
+    16, 5, 2, 12, 15, 24,
+];
+
+/// A check character scheme for very short codes.
+///
+/// Adding 1 error correcting symbol with Reed-Solomon only detects errors,
+/// and it doesn't detect a pair of adjacent characters that were swapped. A
+/// check character from either of these schemes detects every single
+/// character error and every adjacent transposition, so it is a better
+/// choice when a code is too short to spend more than 1 character on error
+/// detection. Neither scheme can correct errors.
+///
+/// Both schemes work directly on z-base-32 characters and add a single
+/// character to the end of the code.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CheckDigit {
+    /// The Damm algorithm, using the quasigroup `x * y = 2x + y` over GF(2^5)
+    Damm,
+    /// The Verhoeff algorithm, using the dihedral group D16
+    Verhoeff,
+}
+
+impl CheckDigit {
+    /// Encode a chunk of input data and add a check character.
+    ///
+    /// `data` and `bits` have the same meaning as for
+    /// [`ChunkEncoder::encode_chunk`](crate::encoder::ChunkEncoder::encode_chunk).
+    /// Since a code has at most 30 characters of data, there is always room for
+    /// the check character.
+    pub fn encode_chunk(&self, data: &[u8], bits: u8) -> Result<EncodedChunk, HumancodeError> {
+        let code = CHUNK_ENCODER_0.encode_chunk(data, bits)?;
+        let (mut quintets, len) = to_quintets(code.raw().as_str())?;
+        quintets[len] = self.check_quintet(&quintets[..len]);
+        Ok(EncodedChunk::from_quintet_buffer(&quintets[..len + 1]))
+    }
+
+    /// Decode a chunk that was encoded with
+    /// [`encode_chunk`](CheckDigit::encode_chunk), checking the check
+    /// character.
+    ///
+    /// `encoded_data` and `bits` have the same meaning as for
+    /// [`ChunkDecoder::decode_chunk`](crate::decoder::ChunkDecoder::decode_chunk).
+    /// If the check character doesn't match, an
+    /// [`InputError`](crate::HumancodeError::InputError) is returned. Since
+    /// errors can't be corrected, the returned `EncodedChunk` is always
+    /// `None`. It is only there so that the result has the same shape as for
+    /// the Reed-Solomon decoders.
+    pub fn decode_chunk(
+        &self,
+        encoded_data: &str,
+        bits: u8,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        let (quintets, len) = to_quintets(encoded_data)?;
+        if len <= 1 {
+            return Err(decode_buffer_smaller_than_ecc());
+        }
+        if self.check_quintet(&quintets[..len - 1]) != quintets[len - 1] {
+            return Err(check_failed());
+        }
+        let data = EncodedChunk::from_quintet_buffer(&quintets[..len - 1]);
+        CHUNK_DECODER_0.decode_chunk(data.raw().as_str(), bits)
+    }
+
+    fn check_quintet(&self, quintets: &[u8]) -> u8 {
+        match self {
+            CheckDigit::Damm => {
+                let interim = quintets
+                    .iter()
+                    .fold(0, |interim, &x| gf_double(interim) ^ x);
+                // The check character brings the final interim value to 0
+                gf_double(interim)
+            }
+            CheckDigit::Verhoeff => {
+                // The check character is at position 0, counting from the
+                // right, so the data starts at position 1
+                let product = quintets
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .fold(0, |product, (i, &x)| {
+                        let mut x = x;
+                        for _ in 0..=i {
+                            x = VERHOEFF_SIGMA[x as usize];
+                        }
+                        d16_mul(product, x)
+                    });
+                d16_inverse(product)
+            }
+        }
+    }
+}
+
+fn to_quintets(encoded_data: &str) -> Result<([u8; 31], usize), HumancodeError> {
+    let mut quintets = [0u8; 31];
+    let mut len = 0;
+    for x in encoded_data.bytes().filter(|&x| x != b'-') {
+        if len >= quintets.len() {
+            return Err(decode_buffer_too_big());
+        }
+        quintets[len] = character_to_quintet(x).map_err(|_| invalid_character())?;
+        len += 1;
+    }
+    Ok((quintets, len))
+}
+
+// Multiply by 2 in GF(2^5) with the polynomial x^5 + x^2 + 1
+fn gf_double(x: u8) -> u8 {
+    let x = x << 1;
+    if x & 0x20 != 0 {
+        x ^ 0x25
+    } else {
+        x
+    }
+}
+
+fn d16_mul(a: u8, b: u8) -> u8 {
+    let (ra, sa) = (a % 16, a / 16);
+    let (rb, sb) = (b % 16, b / 16);
+    let r = if sa == 0 { ra + rb } else { ra + 16 - rb };
+    (sa ^ sb) * 16 + r % 16
+}
+
+fn d16_inverse(a: u8) -> u8 {
+    if a < 16 {
+        (16 - a) % 16
+    } else {
+        // Reflections are their own inverse
+        a
+    }
+}
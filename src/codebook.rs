@@ -0,0 +1,182 @@
+use crate::decode_chunk::{decode_chunk, parse_chunk};
+use crate::encode_chunk::{encode_chunk, ChunkFormat, EncodedChunk};
+use crate::error::{
+    code_not_valid_for_code_book, decode_buffer_too_big, decode_buffer_wrong_size, invalid_bits,
+    HumancodeError,
+};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use libzbase32::low_level_decode::{character_to_quintet, required_octets_buffer_len};
+
+/// A code from a [`CodeBook`] that matched some input, along with the
+/// number of symbols that had to be changed to get from the input to the code
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CodeBookMatch {
+    code: EncodedChunk,
+    distance: u8,
+}
+
+impl CodeBookMatch {
+    /// Get the matching code
+    pub fn code(&self) -> EncodedChunk {
+        self.code
+    }
+
+    /// Get the number of symbols that differ between the input and the
+    /// matching code. Characters in the input that aren't valid z-base-32
+    /// always count as differing.
+    pub fn distance(&self) -> u8 {
+        self.distance
+    }
+}
+
+/// A `CodeBook` holds the set of codes that have been issued, so that user
+/// input can be matched against codes that are known to exist.
+///
+/// Error correction alone will happily correct a mistyped code into any valid
+/// code - including codes that were never issued. Restricting matches to the
+/// codes in a `CodeBook` makes that much less likely.
+#[derive(Clone, Debug)]
+pub struct CodeBook {
+    ecc: u8,
+    bits: u8,
+    code_len: usize,
+    codes: BTreeSet<EncodedChunk>,
+}
+
+impl CodeBook {
+    /// Create an empty `CodeBook` for codes with the given number of error
+    /// correcting symbols and bits.
+    pub fn new(ecc: u8, bits: u8) -> Result<CodeBook, HumancodeError> {
+        if bits == 0 || bits > 150 {
+            return Err(invalid_bits());
+        }
+        // Encoding a value is the easiest way to validate the rest of the
+        // parameters and to find out how long the codes are.
+        let data_len = required_octets_buffer_len(bits as u64)
+            .expect("required_octets_buffer_len() failed - which shouldn't be possible");
+        let code = encode_chunk(&[0u8; 19][..data_len], ecc, bits)?;
+        Ok(CodeBook {
+            ecc,
+            bits,
+            code_len: code.encoded_len(ChunkFormat::Raw),
+            codes: BTreeSet::new(),
+        })
+    }
+
+    /// Add an issued code to the `CodeBook`.
+    ///
+    /// The code must be a valid code for the `CodeBook`'s `ecc` and `bits`
+    /// values. Returns `true` if the code wasn't already present.
+    pub fn insert(&mut self, code: EncodedChunk) -> Result<bool, HumancodeError> {
+        if code.encoded_len(ChunkFormat::Raw) != self.code_len {
+            return Err(code_not_valid_for_code_book());
+        }
+        match decode_chunk(code.raw().as_str(), self.ecc, self.bits) {
+            Ok((_, None)) => Ok(self.codes.insert(code)),
+            _ => Err(code_not_valid_for_code_book()),
+        }
+    }
+
+    /// Remove a code from the `CodeBook`, returning `true` if it was present
+    pub fn remove(&mut self, code: &EncodedChunk) -> bool {
+        self.codes.remove(code)
+    }
+
+    /// Check if a code is in the `CodeBook`
+    pub fn contains(&self, code: &EncodedChunk) -> bool {
+        self.codes.contains(code)
+    }
+
+    /// Get the number of codes in the `CodeBook`
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Check if the `CodeBook` is empty
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Iterate over all of the codes in the `CodeBook`
+    pub fn iter(&self) -> impl Iterator<Item = &EncodedChunk> + '_ {
+        self.codes.iter()
+    }
+
+    /// Find the issued codes closest to what the user typed.
+    ///
+    /// `input` may be in either the "raw" or "pretty" format and may contain
+    /// errors. It is first corrected with the `CodeBook`'s error correcting
+    /// symbols - if that produces a code in the `CodeBook`, it is the only
+    /// possible closest match and is returned on its own. Otherwise, every
+    /// code in the `CodeBook` is compared with the input and all of the codes
+    /// at the smallest distance are returned.
+    ///
+    /// Codes that differ from the input in more than `max_distance` symbols
+    /// are never returned, so an empty result means that nothing matched.
+    /// The input must be the same length as the codes in the `CodeBook`.
+    pub fn find_nearest(
+        &self,
+        input: &str,
+        max_distance: u8,
+    ) -> Result<Vec<CodeBookMatch>, HumancodeError> {
+        let (symbols, len) = input_symbols(input)?;
+        if len != self.code_len {
+            return Err(decode_buffer_wrong_size());
+        }
+        let symbols = &symbols[..len];
+
+        let mut matches = Vec::new();
+
+        // If error correction succeeds, the corrected code is closer to the
+        // input than any other code - so, if its in the CodeBook, we're done.
+        if let Ok(code) = parse_chunk(input, self.ecc, self.bits) {
+            if self.codes.contains(&code) {
+                let distance = symbol_distance(symbols, &code);
+                if distance <= max_distance {
+                    matches.push(CodeBookMatch { code, distance });
+                }
+                return Ok(matches);
+            }
+        }
+
+        let mut best = max_distance;
+        for &code in self.codes.iter() {
+            let distance = symbol_distance(symbols, &code);
+            if distance < best {
+                best = distance;
+                matches.clear();
+            }
+            if distance == best {
+                matches.push(CodeBookMatch { code, distance });
+            }
+        }
+        Ok(matches)
+    }
+}
+
+// Convert the input into quintets, ignoring separators. Characters that
+// aren't valid z-base-32 become `None`.
+fn input_symbols(input: &str) -> Result<([Option<u8>; 31], usize), HumancodeError> {
+    let mut symbols = [None; 31];
+    let mut len = 0;
+    for &x in input.as_bytes().iter() {
+        if x == b'-' {
+            continue;
+        }
+        if len >= symbols.len() {
+            return Err(decode_buffer_too_big());
+        }
+        symbols[len] = character_to_quintet(x).ok();
+        len += 1;
+    }
+    Ok((symbols, len))
+}
+
+fn symbol_distance(symbols: &[Option<u8>], code: &EncodedChunk) -> u8 {
+    symbols
+        .iter()
+        .zip(code.raw().as_str().bytes())
+        .filter(|&(&x, y)| x.is_none() || x != character_to_quintet(y).ok())
+        .count() as u8
+}
@@ -0,0 +1,125 @@
+use crate::{decode_chunk, ChunkFormat, DecodedChunk, EncodedChunk};
+use std::io::{BufRead, Error, ErrorKind, Write};
+
+/// How characters that were changed by error correction are highlighted
+/// by [`confirm_correction`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HighlightStyle {
+    /// Use ANSI escape codes to color the changed characters. Only use
+    /// this if the output is a terminal that supports colors.
+    Ansi,
+    /// Print a line of "^" markers underneath the changed characters.
+    /// This works with any output.
+    Markers,
+}
+
+const TYPED_LABEL: &str = "You entered:    ";
+const CORRECTED_LABEL: &str = "Corrected code: ";
+
+/// Show a code that the user typed alongside the code it was corrected to,
+/// and ask the user to confirm the correction.
+///
+/// Both codes are shown in the "pretty" format, one above the other, with
+/// the characters that differ highlighted according to `style`. The prompt
+/// is written to `output` and the answer is read from `input`.
+///
+/// Returns `true` only if the user answered "y" or "yes".
+pub fn confirm_correction<R: BufRead + ?Sized, W: Write + ?Sized>(
+    typed: &str,
+    corrected: &EncodedChunk,
+    style: HighlightStyle,
+    input: &mut R,
+    output: &mut W,
+) -> std::io::Result<bool> {
+    let mut corrected_buf = [0u8; 38];
+    let corrected_len = corrected
+        .encode_into(ChunkFormat::Pretty, &mut corrected_buf)
+        .expect("encode_into() failed - which shouldn't be possible since the buffer is large enough for any code");
+    let corrected_chars = &corrected_buf[..corrected_len];
+
+    // Re-group what was typed the same way as the corrected code so that
+    // the characters line up - regardless of where the user put separators.
+    let mut typed_chars = typed.chars().filter(|&c| c != '-');
+    let mut typed_line = String::new();
+    let mut corrected_line = String::new();
+    let mut markers = String::new();
+    for &c in corrected_chars.iter() {
+        let c = c as char;
+        if c == '-' {
+            typed_line.push('-');
+            corrected_line.push('-');
+            markers.push(' ');
+            continue;
+        }
+        let t = typed_chars.next().unwrap_or(' ');
+        if t == c {
+            typed_line.push(t);
+            corrected_line.push(c);
+            markers.push(' ');
+        } else {
+            match style {
+                HighlightStyle::Ansi => {
+                    typed_line.push_str(&format!("\x1b[1;31m{}\x1b[0m", t));
+                    corrected_line.push_str(&format!("\x1b[1;32m{}\x1b[0m", c));
+                }
+                HighlightStyle::Markers => {
+                    typed_line.push(t);
+                    corrected_line.push(c);
+                }
+            }
+            markers.push('^');
+        }
+    }
+
+    writeln!(output, "{}{}", TYPED_LABEL, typed_line)?;
+    writeln!(output, "{}{}", CORRECTED_LABEL, corrected_line)?;
+    if style == HighlightStyle::Markers {
+        writeln!(
+            output,
+            "{:width$}{}",
+            "",
+            markers.trim_end(),
+            width = CORRECTED_LABEL.len()
+        )?;
+    }
+    write!(output, "Use the corrected code? [y/N] ")?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// Decode a code that the user typed, asking the user to confirm any
+/// correction before returning the decoded data.
+///
+/// `typed`, `ecc`, and `bits` have the same meaning as for
+/// [`decode_chunk`](crate::decode_chunk()). If no correction was needed, the
+/// user isn't asked anything. Otherwise, [`confirm_correction`] is used to
+/// ask the user.
+///
+/// Returns `Ok(None)` if the user rejected the correction. If the code
+/// couldn't be decoded, an error of kind [`ErrorKind::InvalidData`] that wraps
+/// the [`HumancodeError`](crate::HumancodeError) is returned.
+pub fn decode_chunk_interactive<R: BufRead + ?Sized, W: Write + ?Sized>(
+    typed: &str,
+    ecc: u8,
+    bits: u8,
+    style: HighlightStyle,
+    input: &mut R,
+    output: &mut W,
+) -> std::io::Result<Option<DecodedChunk>> {
+    let (decoded, corrected) =
+        decode_chunk(typed, ecc, bits).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+    match corrected {
+        Some(corrected) => {
+            if confirm_correction(typed, &corrected, style, input, output)? {
+                Ok(Some(decoded))
+            } else {
+                Ok(None)
+            }
+        }
+        None => Ok(Some(decoded)),
+    }
+}
@@ -1,10 +1,16 @@
 use crate::error::{
-    decode_buffer_smaller_than_ecc, decode_buffer_too_big, decode_buffer_wrong_size, invalid_bits,
-    invalid_ecc_len, too_many_errors, HumancodeError,
+    decode_buffer_smaller_than_ecc, decode_buffer_too_big, decode_buffer_wrong_size,
+    decoded_chunk_wrong_size, invalid_bits, invalid_ecc_len, too_many_errors, HumancodeError,
 };
+use crate::rs::{RsCodec, RsParams};
 use crate::smallbytebuf::SmallByteBuf;
 use crate::EncodedChunk;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::convert::TryFrom;
 use core::fmt::{Debug, Formatter};
+use core::ops::Deref;
 use libzbase32::low_level_decode::{
     character_to_quintet, is_last_quintet_valid, quintets_to_octets, required_octets_buffer_len,
 };
@@ -99,17 +105,36 @@ pub const CHUNK_DECODER_30: ChunkDecoder =
 /// A decoded chunk of bytes
 ///
 /// The [`as_bytes`](DecodedChunk::as_bytes) method can be used to
-/// access the underlying bytes.
+/// access the underlying bytes. A `DecodedChunk` can also be converted
+/// into a fixed size array with `TryFrom` - the conversion fails if the
+/// array isn't exactly the same length as the decoded data.
 #[derive(Copy, Clone)]
 pub struct DecodedChunk {
     buf: SmallByteBuf<19>,
 }
 
 impl DecodedChunk {
+    pub(crate) fn from_buf(buf: SmallByteBuf<19>) -> DecodedChunk {
+        DecodedChunk { buf }
+    }
+
     /// Get the underlying decoded bytes
     pub fn as_bytes(&self) -> &[u8] {
         self.buf.as_bytes()
     }
+
+    /// Get the underlying decoded bytes as a `Vec`
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    /// Get the backing array and the number of bytes of it that are used.
+    ///
+    /// All bytes of the array after the used length are 0.
+    pub fn into_inner(self) -> ([u8; 19], usize) {
+        self.buf.into_inner()
+    }
 }
 
 impl AsRef<[u8]> for DecodedChunk {
@@ -118,6 +143,35 @@ impl AsRef<[u8]> for DecodedChunk {
     }
 }
 
+impl Deref for DecodedChunk {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<[u8]> for DecodedChunk {
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> TryFrom<DecodedChunk> for [u8; N] {
+    type Error = HumancodeError;
+
+    fn try_from(value: DecodedChunk) -> Result<[u8; N], HumancodeError> {
+        <[u8; N]>::try_from(value.as_bytes()).map_err(|_| decoded_chunk_wrong_size())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<DecodedChunk> for Vec<u8> {
+    fn from(value: DecodedChunk) -> Vec<u8> {
+        value.to_vec()
+    }
+}
+
 impl Debug for DecodedChunk {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self.as_bytes())
@@ -130,13 +184,61 @@ impl Debug for DecodedChunk {
 // and we want to be consistent.
 #[derive(Debug)]
 pub struct ChunkDecoder {
-    rs_decoder: &'static reed_solomoon_decoder::Decoder,
+    rs_decoder: RsDecoder,
     ecc: u8,
 }
 
+#[derive(Debug)]
+enum RsDecoder {
+    Builtin(&'static reed_solomoon_decoder::Decoder),
+    Custom(RsCodec),
+}
+
 impl ChunkDecoder {
     const fn new(rs_decoder: &'static reed_solomoon_decoder::Decoder, ecc: u8) -> ChunkDecoder {
-        ChunkDecoder { rs_decoder, ecc }
+        ChunkDecoder {
+            rs_decoder: RsDecoder::Builtin(rs_decoder),
+            ecc,
+        }
+    }
+
+    /// Create a `ChunkDecoder` that uses custom Reed-Solomon parameters, to
+    /// decode codes from another implementation.
+    ///
+    /// `params` and `ecc` must match the values used to create the codes - see
+    /// [`ChunkEncoder::with_params`](crate::encoder::ChunkEncoder::with_params).
+    /// `ecc` must be in the range [0,30].
+    pub const fn with_params(params: RsParams, ecc: u8) -> Result<ChunkDecoder, HumancodeError> {
+        match RsCodec::new(params, ecc) {
+            Ok(codec) => Ok(ChunkDecoder {
+                rs_decoder: RsDecoder::Custom(codec),
+                ecc,
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Correct the quintets, returning the corrected codeword and the number of
+    // errors that were corrected
+    fn correct(
+        &self,
+        quintets: &[u8],
+        erase_pos: &[u8],
+    ) -> Result<(SmallByteBuf<31>, usize), HumancodeError> {
+        match &self.rs_decoder {
+            RsDecoder::Builtin(rs_decoder) => {
+                let (out, err_count) = rs_decoder
+                    .correct_err_count(quintets, Some(erase_pos))
+                    .map_err(|_| too_many_errors())?;
+                let mut buf = [0u8; 31];
+                buf[..out.len()].copy_from_slice(&out);
+                Ok((SmallByteBuf::new(buf, out.len() as u8), err_count))
+            }
+            RsDecoder::Custom(codec) => codec
+                .tables()
+                .correct(quintets, erase_pos)
+                .map_err(|_| too_many_errors()),
+        }
     }
 
     /// Decode and correct an encoded message.
@@ -159,113 +261,152 @@ impl ChunkDecoder {
         encoded_data: &str,
         bits: u8,
     ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
-        if bits == 0 || bits > 150 {
-            return Err(invalid_bits());
-        }
+        decode_with(encoded_data, bits, self.ecc, |quintets, erase_pos| {
+            self.correct(quintets, erase_pos)
+        })
+    }
 
-        fn convert_encoded_data_to_quintets(
-            bits: u8,
-            num_quintets: usize,
-            encoded_data: &str,
-        ) -> Result<(SmallByteBuf<31>, SmallByteBuf<31>), HumancodeError> {
-            let mut out_buffer = [0u8; 31];
-            let mut out_idx = 0;
-            let mut erase_pos = [0u8; 31];
-            let mut erase_pos_size = 0;
-
-            for &x in encoded_data.as_bytes().iter() {
-                if x == b'-' {
-                    continue;
-                }
+    /// Parse and correct an encoded message, returning the validated code.
+    ///
+    /// This accepts the same input as [`decode_chunk`](ChunkDecoder::decode_chunk)
+    /// but returns the canonical [`EncodedChunk`] instead of the decoded data.
+    /// If the input contained errors, the corrected code is returned - compare
+    /// the result with the input to find out if a correction was made.
+    pub fn parse_chunk(
+        &self,
+        encoded_data: &str,
+        bits: u8,
+    ) -> Result<EncodedChunk, HumancodeError> {
+        let (_, corrected) = self.decode_chunk(encoded_data, bits)?;
+        canonical_chunk(encoded_data, corrected)
+    }
+}
 
-                if out_idx >= out_buffer.len() {
-                    return Err(decode_buffer_too_big());
-                }
+// Decode and correct an encoded message, using `correct` to run the
+// Reed-Solomon decoder. `correct` is passed the quintets and the positions of
+// any erasures and returns the corrected codeword and the number of errors
+// that were corrected.
+pub(crate) fn decode_with<F>(
+    encoded_data: &str,
+    bits: u8,
+    ecc: u8,
+    correct: F,
+) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError>
+where
+    F: FnOnce(&[u8], &[u8]) -> Result<(SmallByteBuf<31>, usize), HumancodeError>,
+{
+    if bits == 0 || bits > 150 {
+        return Err(invalid_bits());
+    }
 
-                match character_to_quintet(x) {
-                    Ok(x) => {
-                        if out_idx + 1 == num_quintets && !is_last_quintet_valid(bits as u64, x) {
-                            // If we're dealing with the last quintet of the data payload,
-                            // we have to check if its valid given the bits size - since
-                            // libzbase32 doesn't permit for trailing non-zero bits
-                            erase_pos[erase_pos_size] = out_idx as u8;
-                            erase_pos_size += 1;
-                        } else {
-                            out_buffer[out_idx] = x;
-                        }
-                    }
-                    Err(ZBase32Error::InputError(_)) => {
-                        // If the input character is invalid, we can record
-                        // it as an erasure which helps when we apply error
-                        // correction later.
+    fn convert_encoded_data_to_quintets(
+        bits: u8,
+        num_quintets: usize,
+        encoded_data: &str,
+    ) -> Result<(SmallByteBuf<31>, SmallByteBuf<31>), HumancodeError> {
+        let mut out_buffer = [0u8; 31];
+        let mut out_idx = 0;
+        let mut erase_pos = [0u8; 31];
+        let mut erase_pos_size = 0;
+
+        for &x in encoded_data.as_bytes().iter() {
+            if x == b'-' {
+                continue;
+            }
+
+            if out_idx >= out_buffer.len() {
+                return Err(decode_buffer_too_big());
+            }
+
+            match character_to_quintet(x) {
+                Ok(x) => {
+                    if out_idx + 1 == num_quintets && !is_last_quintet_valid(bits as u64, x) {
+                        // If we're dealing with the last quintet of the data payload,
+                        // we have to check if its valid given the bits size - since
+                        // libzbase32 doesn't permit for trailing non-zero bits
                         erase_pos[erase_pos_size] = out_idx as u8;
                         erase_pos_size += 1;
+                    } else {
+                        out_buffer[out_idx] = x;
                     }
-                    Err(ZBase32Error::UsageError(_)) => {
-                        unreachable!("This shouldn't be possible")
-                    }
-                };
-                out_idx += 1;
-            }
-
-            Ok((
-                SmallByteBuf::new(out_buffer, out_idx as u8),
-                SmallByteBuf::new(erase_pos, erase_pos_size as u8),
-            ))
+                }
+                Err(ZBase32Error::InputError(_)) => {
+                    // If the input character is invalid, we can record
+                    // it as an erasure which helps when we apply error
+                    // correction later.
+                    erase_pos[erase_pos_size] = out_idx as u8;
+                    erase_pos_size += 1;
+                }
+                Err(ZBase32Error::UsageError(_)) => {
+                    unreachable!("This shouldn't be possible")
+                }
+            };
+            out_idx += 1;
         }
 
-        let num_quintets = required_quintets_buffer_len(bits as u64)
-            .expect("required_quintets_buffer_len() failed - which shouldn't be possible");
+        Ok((
+            SmallByteBuf::new(out_buffer, out_idx as u8),
+            SmallByteBuf::new(erase_pos, erase_pos_size as u8),
+        ))
+    }
 
-        let (quintet_buffer, erase_pos) =
-            convert_encoded_data_to_quintets(bits, num_quintets, encoded_data)?;
-        if quintet_buffer.len() <= self.ecc as usize {
-            return Err(decode_buffer_smaller_than_ecc());
-        }
+    let num_quintets = required_quintets_buffer_len(bits as u64)
+        .expect("required_quintets_buffer_len() failed - which shouldn't be possible");
 
-        if quintet_buffer.len() - self.ecc as usize != num_quintets {
-            return Err(decode_buffer_wrong_size());
-        }
+    let (quintet_buffer, erase_pos) =
+        convert_encoded_data_to_quintets(bits, num_quintets, encoded_data)?;
+    if quintet_buffer.len() <= ecc as usize {
+        return Err(decode_buffer_smaller_than_ecc());
+    }
 
-        let (out, err_count) = match self
-            .rs_decoder
-            .correct_err_count(quintet_buffer.as_bytes(), Some(erase_pos.as_bytes()))
-        {
-            Ok(r) => r,
-            Err(_) => return Err(too_many_errors()),
-        };
-
-        let corrected_chunk = if err_count > 0 || erase_pos.len() > 0 {
-            Some(EncodedChunk::from_quintet_buffer(&out))
-        } else {
-            None
-        };
-
-        let decoded_data_len = required_octets_buffer_len(bits as u64)
-            .expect("required_octets_buffer_len() failed - which shouldn't be possible");
-        let mut decoded_chunk = DecodedChunk {
-            buf: SmallByteBuf::new([0u8; 19], decoded_data_len as u8),
-        };
-
-        if err_count > 0 || erase_pos.len() > 0 {
-            // If we have some errors, then its possible that our corrected code
-            // is actually wrong. This could cause the final quintet to be an
-            // invalid value for the number of bits. If so, we need to check
-            // for that condition here - otherwise quintets_to_octets() will
-            // fail below.
-            let final_data_quintet = out.data()[out.data().len() - 1];
-            if !is_last_quintet_valid(bits as u64, final_data_quintet) {
-                return Err(too_many_errors());
-            }
+    if quintet_buffer.len() - ecc as usize != num_quintets {
+        return Err(decode_buffer_wrong_size());
+    }
+
+    let (out, err_count) = correct(quintet_buffer.as_bytes(), erase_pos.as_bytes())?;
+    let out_data = &out.as_bytes()[..out.len() - ecc as usize];
+
+    let corrected_chunk = if err_count > 0 || erase_pos.len() > 0 {
+        Some(EncodedChunk::from_quintet_buffer(out.as_bytes()))
+    } else {
+        None
+    };
+
+    let decoded_data_len = required_octets_buffer_len(bits as u64)
+        .expect("required_octets_buffer_len() failed - which shouldn't be possible");
+    let mut decoded_chunk = DecodedChunk {
+        buf: SmallByteBuf::new([0u8; 19], decoded_data_len as u8),
+    };
+
+    if err_count > 0 || erase_pos.len() > 0 {
+        // If we have some errors, then its possible that our corrected code
+        // is actually wrong. This could cause the final quintet to be an
+        // invalid value for the number of bits. If so, we need to check
+        // for that condition here - otherwise quintets_to_octets() will
+        // fail below.
+        let final_data_quintet = out_data[out_data.len() - 1];
+        if !is_last_quintet_valid(bits as u64, final_data_quintet) {
+            return Err(too_many_errors());
         }
+    }
+
+    // This function only fails if the quintets are invalid (ie, >31) or if the final
+    // quintet is not valid for the given bits value. We've already ensured that
+    // neither of those things can be true, so, this shouldn't be able to fail.
+    quintets_to_octets(out_data, decoded_chunk.buf.as_mut_bytes(), bits as u64)
+        .expect("quintets_to_octets() failed - which shouldn't be possible");
 
-        // This function only fails if the quintets are invalid (ie, >31) or if the final
-        // quintet is not valid for the given bits value. We've already ensured that
-        // neither of those things can be true, so, this shouldn't be able to fail.
-        quintets_to_octets(out.data(), decoded_chunk.buf.as_mut_bytes(), bits as u64)
-            .expect("quintets_to_octets() failed - which shouldn't be possible");
+    Ok((decoded_chunk, corrected_chunk))
+}
 
-        Ok((decoded_chunk, corrected_chunk))
+fn canonical_chunk(
+    encoded_data: &str,
+    corrected: Option<EncodedChunk>,
+) -> Result<EncodedChunk, HumancodeError> {
+    match corrected {
+        Some(chunk) => Ok(chunk),
+        // If no correction was needed, the input must already be a valid code
+        None => encoded_data.parse(),
     }
 }
 
@@ -327,3 +468,14 @@ pub fn decode_chunk(
         _ => Err(invalid_ecc_len()),
     }
 }
+
+/// Parse and correct an encoded message, returning the validated code.
+///
+/// `encoded_data`, `ecc`, and `bits` have the same meaning as for
+/// [`decode_chunk`](crate::decode_chunk()). If the input contained errors,
+/// the corrected code is returned - compare the result with the input to
+/// find out if a correction was made.
+pub fn parse_chunk(encoded_data: &str, ecc: u8, bits: u8) -> Result<EncodedChunk, HumancodeError> {
+    let (_, corrected) = decode_chunk(encoded_data, ecc, bits)?;
+    canonical_chunk(encoded_data, corrected)
+}
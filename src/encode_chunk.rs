@@ -1,10 +1,16 @@
 use crate::error::{
-    encode_buffer_doesnt_match_bits, encode_buffer_too_big, invalid_bits, invalid_ecc_len,
+    decode_buffer_too_big, empty_code, encode_buffer_doesnt_match_bits, encode_buffer_too_big,
+    invalid_bits, invalid_character, invalid_ecc_len, output_buffer_too_small,
     total_encode_len_too_long, HumancodeError,
 };
+use crate::numeric::EncodedChunkNumeric;
+use crate::rs::{RsCodec, RsParams};
 use crate::smallbytebuf::SmallByteBuf;
+use core::cmp::Ordering;
 use core::fmt::{Debug, Display, Formatter};
-use libzbase32::low_level_decode::required_octets_buffer_len;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+use libzbase32::low_level_decode::{character_to_quintet, required_octets_buffer_len};
 use libzbase32::low_level_encode::{
     octets_to_quintets, quintet_to_character, required_quintets_buffer_len,
 };
@@ -99,7 +105,11 @@ pub const CHUNK_ENCODER_30: ChunkEncoder =
 /// You can't do much with an `EncodedChunk` directly. Use
 /// the [`raw`](EncodedChunk::raw) or [`pretty`](EncodedChunk::pretty) to
 /// convert the `EncodedChunk` into a type that can be used.
-#[derive(Copy, Clone, Debug)]
+///
+/// `EncodedChunk`, [`EncodedChunkRaw`], and [`EncodedChunkPretty`] are all
+/// compared by their z-base-32 symbols, so the raw and pretty forms of the
+/// same code compare as equal and hash to the same value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct EncodedChunk {
     raw: EncodedChunkRaw,
 }
@@ -118,6 +128,27 @@ impl EncodedChunk {
         }
     }
 
+    // Parse a code without doing any error correction - every character other
+    // than "-" must be a valid z-base-32 character.
+    fn parse(code: &str) -> Result<EncodedChunk, HumancodeError> {
+        let mut quintet_buffer = [0u8; 31];
+        let mut len = 0;
+        for &x in code.as_bytes().iter() {
+            if x == b'-' {
+                continue;
+            }
+            if len >= quintet_buffer.len() {
+                return Err(decode_buffer_too_big());
+            }
+            quintet_buffer[len] = character_to_quintet(x).map_err(|_| invalid_character())?;
+            len += 1;
+        }
+        if len == 0 {
+            return Err(empty_code());
+        }
+        Ok(EncodedChunk::from_quintet_buffer(&quintet_buffer[..len]))
+    }
+
     /// Format the `EncodedChunk` with the "raw" format - just the raw z-base-32 characters
     pub fn raw(self) -> EncodedChunkRaw {
         self.raw
@@ -129,6 +160,127 @@ impl EncodedChunk {
     pub fn pretty(self) -> EncodedChunkPretty {
         EncodedChunkPretty::from_raw(self.raw)
     }
+
+    /// Format the `EncodedChunk` with the "numeric" format - every character
+    /// is written as 2 decimal digits, for entry on a phone keypad. See
+    /// [`EncodedChunkNumeric`].
+    pub fn numeric(self) -> EncodedChunkNumeric {
+        EncodedChunkNumeric::from_raw(self.raw.buf.as_bytes())
+    }
+
+    /// Get the number of symbols that differ between this code and `other`.
+    ///
+    /// Returns `None` if the codes have different lengths.
+    pub fn distance(&self, other: &EncodedChunk) -> Option<u8> {
+        let a = self.raw.buf.as_bytes();
+        let b = other.raw.buf.as_bytes();
+        if a.len() != b.len() {
+            return None;
+        }
+        Some(a.iter().zip(b.iter()).filter(|(x, y)| x != y).count() as u8)
+    }
+
+    /// Get the number of characters in the code when written in the given format
+    pub fn encoded_len(&self, format: ChunkFormat) -> usize {
+        formatted_characters(self.raw.buf.as_bytes(), format).count()
+    }
+
+    /// Write the code in the given format into `out`, returning the number
+    /// of bytes written.
+    ///
+    /// `out` must be at least [`encoded_len`](EncodedChunk::encoded_len) bytes
+    /// long - 31 bytes is always enough for the "raw" format and 38 bytes is
+    /// always enough for the "pretty" format. Nothing is written if an error
+    /// is returned.
+    pub fn encode_into(
+        &self,
+        format: ChunkFormat,
+        out: &mut [u8],
+    ) -> Result<usize, HumancodeError> {
+        if out.len() < self.encoded_len(format) {
+            return Err(output_buffer_too_small());
+        }
+        let mut len = 0;
+        for (x, y) in out
+            .iter_mut()
+            .zip(formatted_characters(self.raw.buf.as_bytes(), format))
+        {
+            *x = y;
+            len += 1;
+        }
+        Ok(len)
+    }
+
+    /// Write the code in the given format to a [`core::fmt::Write`]
+    pub fn write_to<W: core::fmt::Write + ?Sized>(
+        &self,
+        format: ChunkFormat,
+        out: &mut W,
+    ) -> core::fmt::Result {
+        for x in formatted_characters(self.raw.buf.as_bytes(), format) {
+            out.write_char(x as char)?;
+        }
+        Ok(())
+    }
+
+    /// Write the code in the given format to a [`std::io::Write`]
+    #[cfg(feature = "std")]
+    pub fn write_to_io<W: std::io::Write + ?Sized>(
+        &self,
+        format: ChunkFormat,
+        out: &mut W,
+    ) -> std::io::Result<()> {
+        let mut buf = [0u8; 38];
+        let len = self
+            .encode_into(format, &mut buf)
+            .expect("encode_into() failed - which shouldn't be possible since the buffer is large enough for any code");
+        out.write_all(&buf[..len])
+    }
+}
+
+/// The format to use when writing out an [`EncodedChunk`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChunkFormat {
+    /// Just the raw z-base-32 characters - see [`EncodedChunkRaw`]
+    Raw,
+    /// Groups of 4 characters separated by a "-" - see [`EncodedChunkPretty`]
+    Pretty,
+}
+
+// Iterate over the characters of a raw code, adding separators if
+// the "pretty" format is requested.
+pub(crate) fn formatted_characters(
+    raw: &[u8],
+    format: ChunkFormat,
+) -> impl Iterator<Item = u8> + '_ {
+    raw.iter().enumerate().flat_map(move |(i, &x)| {
+        let separator = if format == ChunkFormat::Pretty && i > 0 && i % 4 == 0 {
+            Some(b'-')
+        } else {
+            None
+        };
+        separator.into_iter().chain(core::iter::once(x))
+    })
+}
+
+/// Parses a code in either the "raw" or "pretty" format.
+///
+/// Only the characters are validated - the code is _not_ checked for
+/// errors since that requires knowing the number of bits and error
+/// correcting symbols. Use [`parse_chunk`](crate::parse_chunk()) to get
+/// a code that has been checked and corrected.
+impl FromStr for EncodedChunk {
+    type Err = HumancodeError;
+
+    fn from_str(s: &str) -> Result<EncodedChunk, HumancodeError> {
+        EncodedChunk::parse(s)
+    }
+}
+
+// Compares a code to a string, ignoring any "-" separators in the string.
+fn eq_ignoring_separators(code: &[u8], s: &str) -> bool {
+    let mut chars = s.as_bytes().iter().filter(|&&x| x != b'-');
+    code.iter().all(|x| chars.next() == Some(x)) && chars.next().is_none()
 }
 
 /// A code in the "raw" format - just a list of z-base-32 characters with
@@ -146,6 +298,66 @@ impl EncodedChunkRaw {
     }
 }
 
+impl PartialEq for EncodedChunkRaw {
+    fn eq(&self, other: &EncodedChunkRaw) -> bool {
+        self.buf.as_bytes() == other.buf.as_bytes()
+    }
+}
+
+impl Eq for EncodedChunkRaw {}
+
+impl Hash for EncodedChunkRaw {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.as_bytes().hash(state)
+    }
+}
+
+impl PartialOrd for EncodedChunkRaw {
+    fn partial_cmp(&self, other: &EncodedChunkRaw) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EncodedChunkRaw {
+    fn cmp(&self, other: &EncodedChunkRaw) -> Ordering {
+        // z-base-32 characters aren't in the same order as the quintets
+        // they represent - so, we compare the quintets.
+        fn quintets(raw: &EncodedChunkRaw) -> impl Iterator<Item = u8> + '_ {
+            raw.buf.as_bytes().iter().map(|&x| {
+                character_to_quintet(x).expect("character_to_quintet() failed - which shouldn't be possible since we only store valid values")
+            })
+        }
+        quintets(self).cmp(quintets(other))
+    }
+}
+
+impl PartialEq<EncodedChunkPretty> for EncodedChunkRaw {
+    fn eq(&self, other: &EncodedChunkPretty) -> bool {
+        *self == other.to_raw()
+    }
+}
+
+impl PartialEq<str> for EncodedChunkRaw {
+    /// Compare with a string, ignoring any "-" separators in the string
+    fn eq(&self, other: &str) -> bool {
+        eq_ignoring_separators(self.buf.as_bytes(), other)
+    }
+}
+
+impl PartialEq<&str> for EncodedChunkRaw {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl FromStr for EncodedChunkRaw {
+    type Err = HumancodeError;
+
+    fn from_str(s: &str) -> Result<EncodedChunkRaw, HumancodeError> {
+        Ok(EncodedChunk::parse(s)?.raw())
+    }
+}
+
 impl AsRef<str> for EncodedChunkRaw {
     fn as_ref(&self) -> &str {
         self.as_str()
@@ -180,11 +392,7 @@ impl EncodedChunkPretty {
     fn from_raw(raw: EncodedChunkRaw) -> EncodedChunkPretty {
         let mut pos = 0;
         let mut encoded_data = [0u8; 38];
-        for &x in raw.buf.as_bytes().iter() {
-            if (pos + 1) % 5 == 0 {
-                encoded_data[pos] = b'-';
-                pos += 1;
-            }
+        for x in formatted_characters(raw.buf.as_bytes(), ChunkFormat::Pretty) {
             encoded_data[pos] = x;
             pos += 1;
         }
@@ -193,6 +401,18 @@ impl EncodedChunkPretty {
         }
     }
 
+    fn to_raw(self) -> EncodedChunkRaw {
+        let mut len = 0;
+        let mut raw_data = [0u8; 31];
+        for &x in self.buf.as_bytes().iter().filter(|&&x| x != b'-') {
+            raw_data[len] = x;
+            len += 1;
+        }
+        EncodedChunkRaw {
+            buf: SmallByteBuf::new(raw_data, len as u8),
+        }
+    }
+
     /// Get the code as a `str`
     pub fn as_str(&self) -> &str {
         core::str::from_utf8(&self.buf.as_bytes())
@@ -200,6 +420,61 @@ impl EncodedChunkPretty {
     }
 }
 
+impl PartialEq for EncodedChunkPretty {
+    fn eq(&self, other: &EncodedChunkPretty) -> bool {
+        self.buf.as_bytes() == other.buf.as_bytes()
+    }
+}
+
+impl Eq for EncodedChunkPretty {}
+
+impl Hash for EncodedChunkPretty {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the same way as EncodedChunkRaw so that the raw and pretty
+        // forms of a code hash to the same value.
+        self.to_raw().hash(state)
+    }
+}
+
+impl PartialOrd for EncodedChunkPretty {
+    fn partial_cmp(&self, other: &EncodedChunkPretty) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EncodedChunkPretty {
+    fn cmp(&self, other: &EncodedChunkPretty) -> Ordering {
+        self.to_raw().cmp(&other.to_raw())
+    }
+}
+
+impl PartialEq<EncodedChunkRaw> for EncodedChunkPretty {
+    fn eq(&self, other: &EncodedChunkRaw) -> bool {
+        self.to_raw() == *other
+    }
+}
+
+impl PartialEq<str> for EncodedChunkPretty {
+    /// Compare with a string, ignoring any "-" separators in either value
+    fn eq(&self, other: &str) -> bool {
+        self.to_raw() == *other
+    }
+}
+
+impl PartialEq<&str> for EncodedChunkPretty {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl FromStr for EncodedChunkPretty {
+    type Err = HumancodeError;
+
+    fn from_str(s: &str) -> Result<EncodedChunkPretty, HumancodeError> {
+        Ok(EncodedChunk::parse(s)?.pretty())
+    }
+}
+
 impl AsRef<str> for EncodedChunkPretty {
     fn as_ref(&self) -> &str {
         self.as_str()
@@ -225,17 +500,57 @@ impl Display for EncodedChunkPretty {
 // unclear how valuable that really is, however.
 #[derive(Debug)]
 pub struct ChunkEncoder {
-    rs_encoder: &'static reed_solomoon_encoder::Encoder,
+    rs_encoder: RsEncoder,
     ecc: u8,
 }
 
+#[derive(Debug)]
+enum RsEncoder {
+    Builtin(&'static reed_solomoon_encoder::Encoder),
+    Custom(RsCodec),
+}
+
 impl ChunkEncoder {
     /// Create a new `ChunkEncoder`.
     ///
     /// `ecc` is the number of error correcting symbols to use. It must
-    /// be at least 1 and less than or equal to 30
+    /// be in the range [0,30]
     const fn new(rs_encoder: &'static reed_solomoon_encoder::Encoder, ecc: u8) -> ChunkEncoder {
-        ChunkEncoder { rs_encoder, ecc }
+        ChunkEncoder {
+            rs_encoder: RsEncoder::Builtin(rs_encoder),
+            ecc,
+        }
+    }
+
+    /// Create a `ChunkEncoder` that uses custom Reed-Solomon parameters, to
+    /// create codes that are compatible with another implementation.
+    ///
+    /// `ecc` is the number of error correcting symbols to use and must be in
+    /// the range [0,30]. A code is at most 31 characters long, so each code
+    /// can hold at most `5 * (31 - ecc)` bits of data, up to the usual limit
+    /// of 150. The tables for the code are computed when the
+    /// `ChunkEncoder` is created. Since this is a `const fn`, that can happen
+    /// at compile time:
+    ///
+    /// ```
+    /// use libhumancode::{encoder::ChunkEncoder, RsParams};
+    ///
+    /// const ENCODER: ChunkEncoder = match RsParams::new(0x25, 2, 1) {
+    ///     Ok(params) => match ChunkEncoder::with_params(params, 4) {
+    ///         Ok(encoder) => encoder,
+    ///         Err(_) => panic!("invalid ecc"),
+    ///     },
+    ///     Err(_) => panic!("invalid parameters"),
+    /// };
+    /// ```
+    pub const fn with_params(params: RsParams, ecc: u8) -> Result<ChunkEncoder, HumancodeError> {
+        match RsCodec::new(params, ecc) {
+            Ok(codec) => Ok(ChunkEncoder {
+                rs_encoder: RsEncoder::Custom(codec),
+                ecc,
+            }),
+            Err(err) => Err(err),
+        }
     }
 
     /// Encode a chunk of input data
@@ -252,38 +567,53 @@ impl ChunkEncoder {
     /// _highest_ bit of `data` will be encoded. All remaining bits of `data` must
     /// be 0s or an error will be reported.
     pub fn encode_chunk(&self, data: &[u8], bits: u8) -> Result<EncodedChunk, HumancodeError> {
-        if data.len() > 19 {
-            return Err(encode_buffer_too_big());
-        }
-        if bits == 0 || bits > 150 {
-            return Err(invalid_bits());
-        }
-        if data.len()
-            != required_octets_buffer_len(bits as u64)
-                .expect("required_octets_buffer_len() failed - which shouldn't be possible")
-        {
-            return Err(encode_buffer_doesnt_match_bits());
-        }
-
-        let data_quintets_len = required_quintets_buffer_len(bits as u64)
-            .expect("required_quintets_buffer_len() failed - which shouldn't be possible");
-        let total_len = data_quintets_len + self.ecc as usize;
-
-        if total_len > 31 {
-            return Err(total_encode_len_too_long());
+        let quintets_buffer = data_quintets(data, bits, self.ecc)?;
+        match &self.rs_encoder {
+            RsEncoder::Builtin(rs_encoder) => {
+                let rs_encoded_buffer = rs_encoder
+                    .encode(&quintets_buffer.as_bytes())
+                    .expect("Reed Solomon 32 encode failed - which shouldn't be possible");
+                Ok(EncodedChunk::from_quintet_buffer(&rs_encoded_buffer))
+            }
+            RsEncoder::Custom(codec) => Ok(EncodedChunk::from_quintet_buffer(
+                codec.tables().encode(quintets_buffer.as_bytes()).as_bytes(),
+            )),
         }
+    }
+}
 
-        let mut quintets_buffer = SmallByteBuf::new([0u8; 31], data_quintets_len as u8);
-        octets_to_quintets(data, quintets_buffer.as_mut_bytes(), bits as u64)
-            .expect("octets_to_quintets() failed - which shouldn't be possible");
+// Check the input data and convert it into the quintets to pass to the
+// Reed-Solomon encoder
+pub(crate) fn data_quintets(
+    data: &[u8],
+    bits: u8,
+    ecc: u8,
+) -> Result<SmallByteBuf<31>, HumancodeError> {
+    if data.len() > 19 {
+        return Err(encode_buffer_too_big());
+    }
+    if bits == 0 || bits > 150 {
+        return Err(invalid_bits());
+    }
+    if data.len()
+        != required_octets_buffer_len(bits as u64)
+            .expect("required_octets_buffer_len() failed - which shouldn't be possible")
+    {
+        return Err(encode_buffer_doesnt_match_bits());
+    }
 
-        let rs_encoded_buffer = self
-            .rs_encoder
-            .encode(&quintets_buffer.as_bytes())
-            .expect("Reed Solomon 32 encode failed - which shouldn't be possible");
+    let data_quintets_len = required_quintets_buffer_len(bits as u64)
+        .expect("required_quintets_buffer_len() failed - which shouldn't be possible");
+    let total_len = data_quintets_len + ecc as usize;
 
-        Ok(EncodedChunk::from_quintet_buffer(&rs_encoded_buffer))
+    if total_len > 31 {
+        return Err(total_encode_len_too_long());
     }
+
+    let mut quintets_buffer = SmallByteBuf::new([0u8; 31], data_quintets_len as u8);
+    octets_to_quintets(data, quintets_buffer.as_mut_bytes(), bits as u64)
+        .expect("octets_to_quintets() failed - which shouldn't be possible");
+    Ok(quintets_buffer)
 }
 
 /// Encode a chunk of input data
@@ -0,0 +1,92 @@
+use crate::decode_chunk::DecodedChunk;
+use crate::encode_chunk::EncodedChunk;
+use crate::error::HumancodeError;
+use crate::feistel::{
+    decode_chunk_permuted, encode_chunk_permuted, permute, unpermute, RoundFunction,
+};
+use core::convert::TryInto;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// A `PayloadCipher` encrypts the data in a code so that it can't be read
+/// without a secret key.
+///
+/// This is format-preserving encryption: a Feistel network over exactly
+/// `bits` bits with HMAC-SHA256 as the round function. The encrypted data
+/// has the same number of bits as the original data, so an encrypted code is
+/// exactly as long as an unencrypted one.
+///
+/// Decryption always succeeds - decrypting with the wrong key, or decrypting
+/// a code that was "corrected" into a different code, silently produces the
+/// wrong data. Small payloads can also be attacked by simply collecting
+/// codes, since there are only 2^`bits` possible values. Encrypting a payload
+/// hides its contents, but doesn't stop anyone from guessing valid codes.
+#[derive(Clone)]
+pub struct PayloadCipher {
+    mac: Hmac<Sha256>,
+}
+
+impl PayloadCipher {
+    /// Create a new `PayloadCipher` from a secret key
+    pub fn new(key: &[u8]) -> PayloadCipher {
+        PayloadCipher {
+            mac: Hmac::new_from_slice(key).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    /// Encrypt `data` in place.
+    ///
+    /// `data` and `bits` must be valid parameters for
+    /// [`encode_chunk`](crate::encode_chunk()) - so any unused bits in the
+    /// last byte of `data` must be 0.
+    pub fn encrypt(&self, data: &mut [u8], bits: u8) -> Result<(), HumancodeError> {
+        permute(self, data, bits)
+    }
+
+    /// Decrypt data that was encrypted with [`encrypt`](PayloadCipher::encrypt) in place.
+    pub fn decrypt(&self, data: &mut [u8], bits: u8) -> Result<(), HumancodeError> {
+        unpermute(self, data, bits)
+    }
+
+    /// Encrypt `data` and then encode it - see [`encode_chunk`](crate::encode_chunk())
+    pub fn encode_chunk(
+        &self,
+        data: &[u8],
+        ecc: u8,
+        bits: u8,
+    ) -> Result<EncodedChunk, HumancodeError> {
+        encode_chunk_permuted(self, data, ecc, bits)
+    }
+
+    /// Decode and correct a code and then decrypt it - see
+    /// [`decode_chunk`](crate::decode_chunk())
+    pub fn decode_chunk(
+        &self,
+        encoded_data: &str,
+        ecc: u8,
+        bits: u8,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        decode_chunk_permuted(self, encoded_data, ecc, bits)
+    }
+}
+
+impl RoundFunction for PayloadCipher {
+    fn round(&self, bits: u8, round: u8, value: u128) -> u128 {
+        let mut mac = self.mac.clone();
+        mac.update(&[bits, round]);
+        mac.update(&value.to_le_bytes());
+        let hash = mac.finalize().into_bytes();
+        u128::from_le_bytes(
+            hash[..16]
+                .try_into()
+                .expect("SHA-256 output is longer than 16 bytes"),
+        )
+    }
+}
+
+impl core::fmt::Debug for PayloadCipher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Don't leak the key into logs
+        f.debug_struct("PayloadCipher").finish_non_exhaustive()
+    }
+}
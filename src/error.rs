@@ -1,18 +1,66 @@
 use core::fmt::{Debug, Display, Formatter};
 
+enum InputErrorType {
+    TooManyErrors,
+    InvalidCharacter,
+    EmptyCode,
+    CheckFailed,
+    #[cfg(any(feature = "auth", feature = "totp"))]
+    AuthenticationFailed,
+    Expired,
+    NoMatchingProfile,
+    AmbiguousProfile,
+    #[cfg(feature = "alloc")]
+    NotEnoughShares,
+    #[cfg(feature = "alloc")]
+    InconsistentShares,
+}
+
 /// An `InputErrorCause` can be formatted to get a string
 /// explaining the error.
-///
-/// Currently, there is only one input error message
-/// (that there were too many errors) but more could be added
-/// in the future.
 pub struct InputErrorCause {
-    __hidden: (),
+    typ: InputErrorType,
 }
 
 impl Debug for InputErrorCause {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        write!(f, "There were too many errors in the data to decode")
+        match self.typ {
+            InputErrorType::TooManyErrors => {
+                write!(f, "There were too many errors in the data to decode")
+            }
+            InputErrorType::InvalidCharacter => write!(
+                f,
+                "The code contained a character that isn't valid z-base-32"
+            ),
+            InputErrorType::EmptyCode => write!(f, "The code didn't contain any characters"),
+            InputErrorType::CheckFailed => write!(
+                f,
+                "The check character didn't match - there is an error in the code"
+            ),
+            #[cfg(any(feature = "auth", feature = "totp"))]
+            InputErrorType::AuthenticationFailed => write!(
+                f,
+                "The code didn't match the value expected for the key - the code wasn't created with this key"
+            ),
+            InputErrorType::Expired => write!(f, "The code has expired"),
+            InputErrorType::NoMatchingProfile => write!(
+                f,
+                "The length of the code doesn't match any of the allowed profiles"
+            ),
+            InputErrorType::AmbiguousProfile => write!(
+                f,
+                "The code could be decoded with more than one of the allowed profiles"
+            ),
+            #[cfg(feature = "alloc")]
+            InputErrorType::NotEnoughShares => write!(
+                f,
+                "Not enough complete shares were provided to reconstruct the secret"
+            ),
+            #[cfg(feature = "alloc")]
+            InputErrorType::InconsistentShares => {
+                write!(f, "The codes don't all belong to shares of the same secret")
+            }
+        }
     }
 }
 
@@ -22,6 +70,40 @@ impl Display for InputErrorCause {
     }
 }
 
+impl InputErrorCause {
+    /// Get the kind of the error, for callers that need to handle some
+    /// input errors differently from others
+    pub fn kind(&self) -> InputErrorKind {
+        match self.typ {
+            #[cfg(any(feature = "auth", feature = "totp"))]
+            InputErrorType::AuthenticationFailed => InputErrorKind::AuthenticationFailed,
+            InputErrorType::Expired => InputErrorKind::Expired,
+            _ => InputErrorKind::Other,
+        }
+    }
+}
+
+/// The kind of an [`InputErrorCause`]
+///
+/// More kinds may be added in the future, so matches on this type must
+/// include a wildcard arm.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum InputErrorKind {
+    /// The code was valid, but it wasn't created with the expected key - see
+    /// [`CodeAuthenticator`](crate::CodeAuthenticator) and
+    /// [`Totp`](crate::Totp)
+    AuthenticationFailed,
+
+    /// The code was valid, but it's outside of its validity window - see
+    /// [`Expiry`](crate::Expiry)
+    Expired,
+
+    /// Any other problem with the input - for example, a code with too many
+    /// errors to correct
+    Other,
+}
+
 enum UsageErrorType {
     // Decoder or Encoder errors
     InvalidECCLen,
@@ -31,11 +113,63 @@ enum UsageErrorType {
     EncodeBufferTooBig,
     EncodeBufferDoesntMatchBits,
     TotalEncodeLenTooLong,
+    OutputBufferTooSmall,
 
     // Decoder errors
     DecodeBufferTooBig,
     DecodeBufferSmallerThanEcc,
     DecodeBufferWrongSize,
+    DecodedChunkWrongSize,
+
+    // CodeBook errors
+    #[cfg(feature = "alloc")]
+    CodeNotValidForCodeBook,
+
+    // BitWriter / BitReader errors
+    BitFieldTooWide,
+    BitFieldValueTooBig,
+    BitWriterFull,
+    BitReaderExhausted,
+    BitBufferDoesntMatchBits,
+
+    // Expiry errors
+    InvalidExpiryParameters,
+    TimeBeforeEpoch,
+
+    // Authentication errors
+    #[cfg(feature = "auth")]
+    InvalidTagBits,
+
+    // Secret sharing errors
+    #[cfg(all(feature = "alloc", feature = "rand"))]
+    InvalidShareParameters,
+    #[cfg(feature = "alloc")]
+    InvalidSecretLength,
+
+    // Interleaving errors
+    #[cfg(feature = "alloc")]
+    InvalidInterleaveParameters,
+
+    // Backup sheet errors
+    #[cfg(feature = "alloc")]
+    InvalidSheetLayout,
+
+    // TOTP errors
+    #[cfg(feature = "totp")]
+    InvalidTimeStep,
+
+    // Reed-Solomon parameter errors
+    InvalidRsParams,
+
+    // Rendering errors
+    #[cfg(feature = "png")]
+    InvalidRenderScale,
+
+    // Generation errors
+    #[cfg(feature = "rand")]
+    GenerateAttemptsExhausted,
+    #[cfg(all(feature = "rand", feature = "alloc"))]
+    BatchInfeasible,
 }
 
 /// A `UsageErrorCause` can be formatted to get a string
@@ -52,9 +186,39 @@ impl Debug for UsageErrorCause {
             UsageErrorType::EncodeBufferTooBig => write!(f, "The buffer to encode must be no larger than 19 bytes (up to 150 bits of that can be encoded)"),
             UsageErrorType::EncodeBufferDoesntMatchBits => write!(f, "The size of the encode buffer didn't match the bits parameter"),
             UsageErrorType::TotalEncodeLenTooLong => write!(f, "The size of encoded data after adding ECC symbols would exceed 31 characters"),
+            UsageErrorType::OutputBufferTooSmall => write!(f, "The output buffer is too small to hold the encoded code"),
             UsageErrorType::DecodeBufferTooBig => write!(f, "The buffer to decode contained more than 31 encoded characters"),
             UsageErrorType::DecodeBufferSmallerThanEcc => write!(f, "The buffer to decode was smaller than the number of ECC symbols"),
             UsageErrorType::DecodeBufferWrongSize => write!(f, "The size of the decode buffer didn't match the bits parameter"),
+            UsageErrorType::DecodedChunkWrongSize => write!(f, "The size of the decoded data didn't match the size of the requested array"),
+            #[cfg(feature = "alloc")]
+            UsageErrorType::CodeNotValidForCodeBook => write!(f, "The code isn't a valid code for the ecc and bits values of the code book"),
+            UsageErrorType::BitFieldTooWide => write!(f, "Bit fields must be no wider than 64 bits"),
+            UsageErrorType::BitFieldValueTooBig => write!(f, "The value of the bit field doesn't fit in the requested number of bits"),
+            UsageErrorType::BitWriterFull => write!(f, "Writing the bit field would exceed the limit of 150 bits"),
+            UsageErrorType::BitReaderExhausted => write!(f, "Reading the bit field would read past the end of the data"),
+            UsageErrorType::BitBufferDoesntMatchBits => write!(f, "The size of the buffer to read bit fields from didn't match the bits parameter"),
+            UsageErrorType::InvalidExpiryParameters => write!(f, "The time bits must be in the range [1,64], the resolution must be at least 1 second, and the lifetime must be shorter than the range of the timestamp"),
+            UsageErrorType::TimeBeforeEpoch => write!(f, "The current time is before the epoch"),
+            #[cfg(feature = "auth")]
+            UsageErrorType::InvalidTagBits => write!(f, "The number of tag bits must be at least 1 and the data and tag bits together must be no more than 150"),
+            #[cfg(all(feature = "alloc", feature = "rand"))]
+            UsageErrorType::InvalidShareParameters => write!(f, "The threshold must be at least 1 and no more than the number of shares"),
+            #[cfg(feature = "alloc")]
+            UsageErrorType::InvalidSecretLength => write!(f, "The secret must be at least 1 byte long and fit in 16 codes per share with the given number of ECC symbols"),
+            #[cfg(feature = "alloc")]
+            UsageErrorType::InvalidInterleaveParameters => write!(f, "The depth must be in the range [1,8] and the bits must be at least 1 and no more than 150 times the depth"),
+            #[cfg(feature = "alloc")]
+            UsageErrorType::InvalidSheetLayout => write!(f, "Each line of a backup sheet must hold at least 1 byte and the data must fit in 128 lines"),
+            #[cfg(feature = "totp")]
+            UsageErrorType::InvalidTimeStep => write!(f, "The time step must be at least 1 second"),
+            UsageErrorType::InvalidRsParams => write!(f, "The primitive polynomial must be an irreducible polynomial of degree 5, the generator must not be 0 or 1, and the first consecutive root must be in the range [0,30]"),
+            #[cfg(feature = "png")]
+            UsageErrorType::InvalidRenderScale => write!(f, "The scale must be in the range [1,64]"),
+            #[cfg(feature = "rand")]
+            UsageErrorType::GenerateAttemptsExhausted => write!(f, "No acceptable code was generated within the maximum number of attempts"),
+            #[cfg(all(feature = "rand", feature = "alloc"))]
+            UsageErrorType::BatchInfeasible => write!(f, "The requested number of codes can't exist for the given bits, ecc, and minimum distance"),
         }
     }
 }
@@ -101,7 +265,66 @@ impl Display for HumancodeError {
 impl std::error::Error for HumancodeError {}
 
 pub const fn too_many_errors() -> HumancodeError {
-    HumancodeError::InputError(InputErrorCause { __hidden: () })
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::TooManyErrors,
+    })
+}
+
+pub const fn invalid_character() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::InvalidCharacter,
+    })
+}
+
+pub const fn empty_code() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::EmptyCode,
+    })
+}
+
+pub const fn check_failed() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::CheckFailed,
+    })
+}
+
+pub const fn no_matching_profile() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::NoMatchingProfile,
+    })
+}
+
+pub const fn ambiguous_profile() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::AmbiguousProfile,
+    })
+}
+
+#[cfg(any(feature = "auth", feature = "totp"))]
+pub const fn authentication_failed() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::AuthenticationFailed,
+    })
+}
+
+pub const fn expired() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::Expired,
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub const fn not_enough_shares() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::NotEnoughShares,
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub const fn inconsistent_shares() -> HumancodeError {
+    HumancodeError::InputError(InputErrorCause {
+        typ: InputErrorType::InconsistentShares,
+    })
 }
 
 pub const fn invalid_ecc_len() -> HumancodeError {
@@ -134,6 +357,12 @@ pub const fn total_encode_len_too_long() -> HumancodeError {
     })
 }
 
+pub const fn output_buffer_too_small() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::OutputBufferTooSmall,
+    })
+}
+
 pub const fn decode_buffer_too_big() -> HumancodeError {
     HumancodeError::UsageError(UsageErrorCause {
         typ: UsageErrorType::DecodeBufferTooBig,
@@ -151,3 +380,127 @@ pub const fn decode_buffer_wrong_size() -> HumancodeError {
         typ: UsageErrorType::DecodeBufferWrongSize,
     })
 }
+
+pub const fn decoded_chunk_wrong_size() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::DecodedChunkWrongSize,
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub const fn code_not_valid_for_code_book() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::CodeNotValidForCodeBook,
+    })
+}
+
+pub const fn bit_field_too_wide() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::BitFieldTooWide,
+    })
+}
+
+pub const fn bit_field_value_too_big() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::BitFieldValueTooBig,
+    })
+}
+
+pub const fn bit_writer_full() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::BitWriterFull,
+    })
+}
+
+pub const fn bit_reader_exhausted() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::BitReaderExhausted,
+    })
+}
+
+pub const fn bit_buffer_doesnt_match_bits() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::BitBufferDoesntMatchBits,
+    })
+}
+
+pub const fn invalid_expiry_parameters() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidExpiryParameters,
+    })
+}
+
+pub const fn time_before_epoch() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::TimeBeforeEpoch,
+    })
+}
+
+#[cfg(feature = "auth")]
+pub const fn invalid_tag_bits() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidTagBits,
+    })
+}
+
+#[cfg(all(feature = "alloc", feature = "rand"))]
+pub const fn invalid_share_parameters() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidShareParameters,
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub const fn invalid_secret_length() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidSecretLength,
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub const fn invalid_interleave_parameters() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidInterleaveParameters,
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub const fn invalid_sheet_layout() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidSheetLayout,
+    })
+}
+
+#[cfg(feature = "totp")]
+pub const fn invalid_time_step() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidTimeStep,
+    })
+}
+
+pub const fn invalid_rs_params() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidRsParams,
+    })
+}
+
+#[cfg(feature = "png")]
+pub const fn invalid_render_scale() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::InvalidRenderScale,
+    })
+}
+
+#[cfg(feature = "rand")]
+pub const fn generate_attempts_exhausted() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::GenerateAttemptsExhausted,
+    })
+}
+
+#[cfg(all(feature = "rand", feature = "alloc"))]
+pub const fn batch_infeasible() -> HumancodeError {
+    HumancodeError::UsageError(UsageErrorCause {
+        typ: UsageErrorType::BatchInfeasible,
+    })
+}
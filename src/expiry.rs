@@ -0,0 +1,150 @@
+use crate::bits::{mask, BitReader, BitWriter};
+use crate::decode_chunk::{decode_chunk, DecodedChunk};
+use crate::encode_chunk::{encode_chunk, EncodedChunk};
+use crate::error::{
+    encode_buffer_doesnt_match_bits, expired, invalid_bits, invalid_expiry_parameters,
+    time_before_epoch, HumancodeError,
+};
+use crate::smallbytebuf::SmallByteBuf;
+
+/// An `Expiry` packs a coarse timestamp alongside the data in a code, so that
+/// codes stop being accepted after a fixed lifetime.
+///
+/// All times are given in seconds - for example, seconds since the Unix epoch.
+/// The timestamp is stored as the number of whole `resolution` periods since
+/// `epoch`, using `time_bits` bits. Only the lowest `time_bits` bits of the
+/// timestamp are stored, so the timestamp may wrap around - which is fine as
+/// long as the lifetime is shorter than the range of the timestamp.
+///
+/// The lifetime is rounded up to a multiple of the resolution. Since the
+/// timestamp is rounded down to the resolution, a code is accepted for at
+/// least `lifetime` seconds, but less than the rounded lifetime plus
+/// `resolution` seconds.
+///
+/// An `Expiry` doesn't stop anyone from creating a code with a later
+/// timestamp, so it should only be used with codes that are hard to guess.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Expiry {
+    epoch: u64,
+    resolution: u64,
+    time_bits: u8,
+    lifetime_periods: u64,
+}
+
+impl Expiry {
+    /// Create a new `Expiry`.
+    ///
+    /// `time_bits` must be in the range [1,64] and `resolution` must be at
+    /// least 1. The lifetime must fit in the range of the timestamp - the
+    /// lifetime divided by the resolution, rounded up, must be less than
+    /// `2^time_bits - 1`.
+    pub fn new(
+        epoch: u64,
+        resolution: u64,
+        time_bits: u8,
+        lifetime: u64,
+    ) -> Result<Expiry, HumancodeError> {
+        if time_bits == 0 || time_bits > 64 || resolution == 0 {
+            return Err(invalid_expiry_parameters());
+        }
+        let lifetime_periods = lifetime.div_ceil(resolution);
+        if lifetime_periods >= mask(time_bits) as u64 {
+            return Err(invalid_expiry_parameters());
+        }
+        Ok(Expiry {
+            epoch,
+            resolution,
+            time_bits,
+            lifetime_periods,
+        })
+    }
+
+    /// Get the number of timestamp bits that are added to each code
+    pub fn time_bits(&self) -> u8 {
+        self.time_bits
+    }
+
+    /// Encode `data` along with the timestamp for `now`.
+    ///
+    /// `data`, `ecc`, and `bits` have the same meaning as for
+    /// [`encode_chunk`](crate::encode_chunk()) - `bits` is the number of
+    /// bits of `data`, not including the timestamp. `bits` plus `time_bits`
+    /// must be no more than 150.
+    pub fn encode_chunk(
+        &self,
+        data: &[u8],
+        ecc: u8,
+        bits: u8,
+        now: u64,
+    ) -> Result<EncodedChunk, HumancodeError> {
+        let total_bits = self.total_bits(bits)?;
+        if data.len() != (bits as usize).div_ceil(8) {
+            return Err(encode_buffer_doesnt_match_bits());
+        }
+        // Like encode_chunk(), all unused bits of data must be 0
+        let unused_bits = data.len() * 8 - bits as usize;
+        if data[data.len() - 1] & ((1u16 << unused_bits) - 1) as u8 != 0 {
+            return Err(encode_buffer_doesnt_match_bits());
+        }
+        let timestamp = self.periods_since_epoch(now)? & mask(self.time_bits) as u64;
+
+        let mut writer = BitWriter::new();
+        writer.write_leading_bits(data, bits)?;
+        writer.write(timestamp, self.time_bits)?;
+        encode_chunk(writer.as_bytes(), ecc, total_bits)
+    }
+
+    /// Decode and correct a code and then check that it hasn't expired.
+    ///
+    /// `encoded_data`, `ecc`, and `bits` have the same meaning as for
+    /// [`decode_chunk`](crate::decode_chunk()) - `bits` is the number of
+    /// bits of data, not including the timestamp. The returned
+    /// [`DecodedChunk`] only contains the data.
+    ///
+    /// If the code was created more than the lifetime before `now`, an
+    /// [`InputError`](crate::HumancodeError::InputError) with the
+    /// [`Expired`](crate::InputErrorKind::Expired) kind is returned.
+    /// Codes with a timestamp after `now` are treated as having wrapped
+    /// around, so they are reported as expired as well.
+    pub fn decode_chunk(
+        &self,
+        encoded_data: &str,
+        ecc: u8,
+        bits: u8,
+        now: u64,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        let total_bits = self.total_bits(bits)?;
+        let now_periods = self.periods_since_epoch(now)?;
+        let (decoded, corrected) = decode_chunk(encoded_data, ecc, total_bits)?;
+
+        let mut reader = BitReader::new(decoded.as_bytes(), total_bits)?;
+        let mut data = [0u8; 19];
+        let data_len = (bits as usize).div_ceil(8);
+        reader.read_leading_bits(&mut data[..data_len], bits)?;
+        let timestamp = reader.read(self.time_bits)?;
+
+        let age = now_periods.wrapping_sub(timestamp) & mask(self.time_bits) as u64;
+        if age > self.lifetime_periods {
+            return Err(expired());
+        }
+
+        Ok((
+            DecodedChunk::from_buf(SmallByteBuf::new(data, data_len as u8)),
+            corrected,
+        ))
+    }
+
+    fn total_bits(&self, bits: u8) -> Result<u8, HumancodeError> {
+        if bits == 0 || bits as usize + self.time_bits as usize > 150 {
+            return Err(invalid_bits());
+        }
+        Ok(bits + self.time_bits)
+    }
+
+    fn periods_since_epoch(&self, now: u64) -> Result<u64, HumancodeError> {
+        match now.checked_sub(self.epoch) {
+            Some(elapsed) => Ok(elapsed / self.resolution),
+            None => Err(time_before_epoch()),
+        }
+    }
+}
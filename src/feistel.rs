@@ -0,0 +1,149 @@
+use crate::bits::{mask, BitReader, BitWriter};
+use crate::decode_chunk::{decode_chunk, DecodedChunk};
+use crate::encode_chunk::{encode_chunk, EncodedChunk};
+use crate::error::{
+    encode_buffer_doesnt_match_bits, encode_buffer_too_big, invalid_bits, HumancodeError,
+};
+use crate::smallbytebuf::SmallByteBuf;
+
+// FF1 uses 10 Feistel rounds and FF3 uses 8, so 10 is plenty for our purposes.
+const ROUNDS: u8 = 10;
+
+// The keyed function that a Feistel network mixes into each half of the
+// value. `value` is the other half, and only the low bits of the result that
+// fit in the half being updated are used.
+pub(crate) trait RoundFunction {
+    fn round(&self, bits: u8, round: u8, value: u128) -> u128;
+}
+
+// Permute exactly `bits` bits of `data` in place
+pub(crate) fn permute<R: RoundFunction>(
+    round_function: &R,
+    data: &mut [u8],
+    bits: u8,
+) -> Result<(), HumancodeError> {
+    run(round_function, data, bits, false)
+}
+
+// Reverse `permute()` in place
+pub(crate) fn unpermute<R: RoundFunction>(
+    round_function: &R,
+    data: &mut [u8],
+    bits: u8,
+) -> Result<(), HumancodeError> {
+    run(round_function, data, bits, true)
+}
+
+// Copy `data` into a buffer, permute it, and then encode it
+pub(crate) fn encode_chunk_permuted<R: RoundFunction>(
+    round_function: &R,
+    data: &[u8],
+    ecc: u8,
+    bits: u8,
+) -> Result<EncodedChunk, HumancodeError> {
+    let mut buf = [0u8; 19];
+    if data.len() > buf.len() {
+        return Err(encode_buffer_too_big());
+    }
+    let buf = &mut buf[..data.len()];
+    buf.copy_from_slice(data);
+    permute(round_function, buf, bits)?;
+    encode_chunk(buf, ecc, bits)
+}
+
+// Decode a code and then reverse the permutation of the decoded data
+pub(crate) fn decode_chunk_permuted<R: RoundFunction>(
+    round_function: &R,
+    encoded_data: &str,
+    ecc: u8,
+    bits: u8,
+) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+    let (decoded, corrected) = decode_chunk(encoded_data, ecc, bits)?;
+    let (mut buf, len) = decoded.into_inner();
+    unpermute(round_function, &mut buf[..len], bits)?;
+    Ok((
+        DecodedChunk::from_buf(SmallByteBuf::new(buf, len as u8)),
+        corrected,
+    ))
+}
+
+// Each round XORs the output of the round function into one half of the
+// value, using the round number and the other half as input. Running the
+// rounds in reverse order undoes the permutation.
+fn run<R: RoundFunction>(
+    round_function: &R,
+    data: &mut [u8],
+    bits: u8,
+    inverse: bool,
+) -> Result<(), HumancodeError> {
+    let (mut left, mut right) = split(data, bits)?;
+    let (left_width, right_width) = half_widths(bits);
+    for i in 0..ROUNDS {
+        let round = if inverse { ROUNDS - 1 - i } else { i };
+        if round % 2 == 0 {
+            left ^= round_function.round(bits, round, right) & mask(left_width);
+        } else {
+            right ^= round_function.round(bits, round, left) & mask(right_width);
+        }
+    }
+    join(data, bits, left, right);
+    Ok(())
+}
+
+// The left half gets the extra bit if `bits` is odd
+fn half_widths(bits: u8) -> (u8, u8) {
+    let left = bits.div_ceil(2);
+    (left, bits - left)
+}
+
+fn split(data: &[u8], bits: u8) -> Result<(u128, u128), HumancodeError> {
+    if bits == 0 || bits > 150 {
+        return Err(invalid_bits());
+    }
+    if data.len() != (bits as usize).div_ceil(8) {
+        return Err(encode_buffer_doesnt_match_bits());
+    }
+    // Like encode_chunk(), all unused bits of data must be 0
+    let unused_bits = data.len() * 8 - bits as usize;
+    if data[data.len() - 1] & ((1u16 << unused_bits) - 1) as u8 != 0 {
+        return Err(encode_buffer_doesnt_match_bits());
+    }
+    let mut reader = BitReader::new(data, bits).expect(
+        "BitReader::new() failed - which shouldn't be possible since we validated the parameters",
+    );
+    let (left_width, right_width) = half_widths(bits);
+    Ok((
+        read_wide(&mut reader, left_width),
+        read_wide(&mut reader, right_width),
+    ))
+}
+
+fn join(data: &mut [u8], bits: u8, left: u128, right: u128) {
+    let (left_width, right_width) = half_widths(bits);
+    let mut writer = BitWriter::new();
+    write_wide(&mut writer, left, left_width);
+    write_wide(&mut writer, right, right_width);
+    data.copy_from_slice(writer.as_bytes());
+}
+
+fn read_wide(reader: &mut BitReader, width: u8) -> u128 {
+    let high_width = width.saturating_sub(64);
+    let high = reader
+        .read(high_width)
+        .expect("read() failed - which shouldn't be possible since the halves add up to bits");
+    let low = reader
+        .read(width - high_width)
+        .expect("read() failed - which shouldn't be possible since the halves add up to bits");
+    ((high as u128) << (width - high_width)) | low as u128
+}
+
+fn write_wide(writer: &mut BitWriter, value: u128, width: u8) {
+    let high_width = width.saturating_sub(64);
+    let low_width = width - high_width;
+    writer
+        .write((value >> low_width) as u64, high_width)
+        .expect("write() failed - which shouldn't be possible since the halves add up to bits");
+    writer
+        .write((value & mask(low_width)) as u64, low_width)
+        .expect("write() failed - which shouldn't be possible since the halves add up to bits");
+}
@@ -0,0 +1,61 @@
+use crate::decode_chunk::DecodedChunk;
+use crate::encode_chunk::{encode_chunk, ChunkEncoder, EncodedChunk};
+use crate::error::{invalid_bits, HumancodeError};
+use crate::smallbytebuf::SmallByteBuf;
+use libzbase32::low_level_decode::required_octets_buffer_len;
+use rand_core::{CryptoRng, RngCore};
+
+// Generate `bits` random bits. Any unused bits of the final byte are zeroed
+// since encode_chunk() requires that.
+fn random_data<R: RngCore + CryptoRng + ?Sized>(
+    bits: u8,
+    rng: &mut R,
+) -> Result<SmallByteBuf<19>, HumancodeError> {
+    if bits == 0 || bits > 150 {
+        return Err(invalid_bits());
+    }
+    let len = required_octets_buffer_len(bits as u64)
+        .expect("required_octets_buffer_len() failed - which shouldn't be possible");
+    let mut data = SmallByteBuf::new([0u8; 19], len as u8);
+    rng.fill_bytes(data.as_mut_bytes());
+    let unused_bits = len * 8 - bits as usize;
+    data.as_mut_bytes()[len - 1] &= 0xffu8 << unused_bits;
+    Ok(data)
+}
+
+impl ChunkEncoder {
+    /// Generate a random code
+    ///
+    /// `bits` random bits are generated using `rng` and then encoded. `bits`
+    /// must be at least 1 and less than or equal to 150.
+    ///
+    /// On success, a tuple of the random data and the code is returned.
+    pub fn generate<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        bits: u8,
+        rng: &mut R,
+    ) -> Result<(DecodedChunk, EncodedChunk), HumancodeError> {
+        let data = random_data(bits, rng)?;
+        let encoded = self.encode_chunk(data.as_bytes(), bits)?;
+        Ok((DecodedChunk::from_buf(data), encoded))
+    }
+}
+
+/// Generate a random code
+///
+/// `ecc` indicates the number of error correcting symbols to use and must
+/// between 0 and 30, inclusive.
+///
+/// `bits` random bits are generated using `rng` and then encoded. `bits`
+/// must be at least 1 and less than or equal to 150.
+///
+/// On success, a tuple of the random data and the code is returned.
+pub fn generate<R: RngCore + CryptoRng + ?Sized>(
+    ecc: u8,
+    bits: u8,
+    rng: &mut R,
+) -> Result<(DecodedChunk, EncodedChunk), HumancodeError> {
+    let data = random_data(bits, rng)?;
+    let encoded = encode_chunk(data.as_bytes(), ecc, bits)?;
+    Ok((DecodedChunk::from_buf(data), encoded))
+}
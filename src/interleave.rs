@@ -0,0 +1,175 @@
+use crate::decode_chunk::decode_chunk;
+use crate::encode_chunk::{encode_chunk, formatted_characters, ChunkFormat};
+use crate::error::{
+    decode_buffer_wrong_size, encode_buffer_doesnt_match_bits, invalid_interleave_parameters,
+    too_many_errors, HumancodeError,
+};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+const MAX_DEPTH: u8 = 8;
+
+/// A code that is made up of several Reed-Solomon codewords, interleaved
+/// character by character. See [`encode_interleaved`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InterleavedCode {
+    raw: String,
+}
+
+impl InterleavedCode {
+    /// Get the code in the "raw" format - just the z-base-32 characters
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Get the code in the "pretty" format - every group of 4 characters is
+    /// separated by a "-"
+    pub fn pretty(&self) -> String {
+        formatted_characters(self.raw.as_bytes(), ChunkFormat::Pretty)
+            .map(char::from)
+            .collect()
+    }
+}
+
+impl Display for InterleavedCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.pretty())
+    }
+}
+
+/// Encode up to `150 * depth` bits of data into a single code longer than
+/// 31 characters.
+///
+/// The data is split evenly into `depth` parts, which are each encoded as
+/// their own codeword with `ecc` error correcting symbols. The codewords are
+/// then interleaved one character at a time, so character `i` of the code
+/// belongs to codeword `i % depth`. A burst of errors - like a smudged group
+/// of characters - is spread across all of the codewords. Each codeword can
+/// correct `ecc / 2` errors, rounded down, so a burst of up to
+/// `depth * (ecc / 2)` characters can be corrected.
+///
+/// `data` must be exactly as long as needed for `bits` and any unused bits
+/// in the last byte must be 0. `depth` must be in the range [1,8] and each
+/// codeword - `bits / depth`, rounded up, plus `ecc` symbols - must fit in
+/// 31 characters.
+pub fn encode_interleaved(
+    data: &[u8],
+    bits: u16,
+    ecc: u8,
+    depth: u8,
+) -> Result<InterleavedCode, HumancodeError> {
+    let codeword_bits = codeword_bits(bits, depth)?;
+    if data.len() != (bits as usize).div_ceil(8)
+        || (bits as usize..data.len() * 8).any(|i| bit(data, i))
+    {
+        return Err(encode_buffer_doesnt_match_bits());
+    }
+
+    let mut codewords = Vec::with_capacity(depth as usize);
+    for i in 0..depth as usize {
+        let mut part = [0u8; 19];
+        let start = i * codeword_bits as usize;
+        for j in 0..codeword_bits as usize {
+            if start + j < bits as usize && bit(data, start + j) {
+                part[j / 8] |= 0x80 >> (j % 8);
+            }
+        }
+        let len = (codeword_bits as usize).div_ceil(8);
+        codewords.push(encode_chunk(&part[..len], ecc, codeword_bits)?.raw());
+    }
+    Ok(interleave(&codewords))
+}
+
+/// Decode and correct a code that was created by [`encode_interleaved`].
+///
+/// `bits`, `ecc`, and `depth` must match the values that were used to create
+/// the code. Any "-" characters in `encoded_data` are ignored. Each codeword
+/// is corrected separately and the data is returned along with the
+/// corrected code, if there was an error in the input.
+pub fn decode_interleaved(
+    encoded_data: &str,
+    bits: u16,
+    ecc: u8,
+    depth: u8,
+) -> Result<(Vec<u8>, Option<InterleavedCode>), HumancodeError> {
+    let codeword_bits = codeword_bits(bits, depth)?;
+    // Any character that isn't ASCII can't be valid - it is replaced with
+    // "!" so that it is treated as a single erasure.
+    let characters: Vec<u8> = encoded_data
+        .chars()
+        .filter(|&c| c != '-')
+        .map(|c| if c.is_ascii() { c as u8 } else { b'!' })
+        .collect();
+    if characters.is_empty() || characters.len() % depth as usize != 0 {
+        return Err(decode_buffer_wrong_size());
+    }
+
+    let mut data = vec![0u8; (bits as usize).div_ceil(8)];
+    let mut corrected_codewords = Vec::with_capacity(depth as usize);
+    let mut any_corrected = false;
+    for i in 0..depth as usize {
+        let codeword: Vec<u8> = characters
+            .iter()
+            .skip(i)
+            .step_by(depth as usize)
+            .copied()
+            .collect();
+        let codeword = core::str::from_utf8(&codeword)
+            .expect("Codeword isn't valid utf-8 - which shouldn't be possible since it's ASCII");
+        let (decoded, corrected) = decode_chunk(codeword, ecc, codeword_bits)?;
+
+        let start = i * codeword_bits as usize;
+        for j in 0..codeword_bits as usize {
+            if !bit(decoded.as_bytes(), j) {
+                continue;
+            }
+            // Padding past the end of the data must be 0 - otherwise, the
+            // code was "corrected" into a different code.
+            if start + j >= bits as usize {
+                return Err(too_many_errors());
+            }
+            data[(start + j) / 8] |= 0x80 >> ((start + j) % 8);
+        }
+
+        match corrected {
+            Some(code) => {
+                any_corrected = true;
+                corrected_codewords.push(String::from(code.raw().as_str()));
+            }
+            None => corrected_codewords.push(String::from(codeword)),
+        }
+    }
+
+    let corrected = if any_corrected {
+        Some(interleave(&corrected_codewords))
+    } else {
+        None
+    };
+    Ok((data, corrected))
+}
+
+// The number of bits in each codeword - the data is split evenly, with the
+// last codeword padded with 0s.
+fn codeword_bits(bits: u16, depth: u8) -> Result<u8, HumancodeError> {
+    if depth == 0 || depth > MAX_DEPTH || bits == 0 || bits > 150 * depth as u16 {
+        return Err(invalid_interleave_parameters());
+    }
+    Ok(bits.div_ceil(depth as u16) as u8)
+}
+
+fn interleave<S: AsRef<str>>(codewords: &[S]) -> InterleavedCode {
+    let len = codewords[0].as_ref().len();
+    let mut raw = String::with_capacity(len * codewords.len());
+    for j in 0..len {
+        for codeword in codewords.iter() {
+            raw.push(codeword.as_ref().as_bytes()[j] as char);
+        }
+    }
+    InterleavedCode { raw }
+}
+
+fn bit(data: &[u8], index: usize) -> bool {
+    data[index / 8] & (0x80 >> (index % 8)) != 0
+}
@@ -20,7 +20,12 @@
 //! it. If we have an input with 3 errors, we will always report it as
 //! incorrect. However, if we have an input with 4 errors, we might
 //! accidentally "correct" it to an invalid code. As such, its highly
-//! recommended to confirm code corrections with the user.
+//! recommended to confirm code corrections with the user - when the
+//! "std" feature is enabled, [`decode_chunk_interactive`] can be used to
+//! do that from a terminal.
+//!
+//! If the set of codes that were issued is known, the "alloc" feature
+//! provides `CodeBook`, which only ever corrects input to one of those codes.
 //!
 //! ## Example
 //!
@@ -48,7 +53,134 @@
 //!
 //! ## No_std
 //!
-//! No_std mode may be activated by disabling the "std" feature.
+//! No_std mode may be activated by disabling the "std" feature. The "alloc"
+//! feature may be enabled in no_std mode to enable APIs that require an
+//! allocator.
+//!
+//! ## Random codes
+//!
+//! Enabling the "rand" feature adds `generate` to create random codes
+//! using any cryptographically secure `rand_core` RNG.
+//!
+//! With the "alloc" feature as well, `generate_batch` creates many unique
+//! codes at once that are all at least a given number of symbols apart.
+//!
+//! ## Obfuscating IDs
+//!
+//! `KeyedPermutation` shuffles values of a fixed number of bits with a secret
+//! key before they are encoded, so that sequential IDs produce unrelated
+//! looking codes that can still be decoded back to the ID with the key.
+//!
+//! ## Authenticated codes
+//!
+//! Error correction only protects against typos. Enabling the "auth" feature
+//! adds `CodeAuthenticator`, which uses part of the 150 bit budget for a
+//! truncated HMAC-SHA256 tag so that codes can't be created without a
+//! secret key.
+//!
+//! ## Encrypted codes
+//!
+//! Enabling the "encrypt" feature adds `PayloadCipher`, which encrypts the
+//! data in a code with a secret key before it is encoded and decrypts it
+//! after it is corrected. The encrypted data has exactly the same number of
+//! bits, so encrypted codes are no longer than regular codes.
+//!
+//! ## Expiring codes
+//!
+//! `Expiry` stores a timestamp with a configurable epoch, resolution, and
+//! number of bits alongside the data in a code. Decoding takes the current
+//! time and reports codes that are older than the configured lifetime with
+//! an `InputError` of the `InputErrorKind::Expired` kind.
+//!
+//! ## Time-based one time codes
+//!
+//! Enabling the "totp" feature adds `Totp`, which derives codes from a
+//! shared secret and the current time as described in RFC 6238. Typos are
+//! corrected before a code is compared, and adjacent time steps can be
+//! accepted to allow for clock differences.
+//!
+//! ## Secret sharing
+//!
+//! With the "rand" and "alloc" features, `split_secret` splits a secret into
+//! shares with Shamir's secret sharing and encodes each share as one or
+//! more codes - for example, to hand out a recovery key on paper.
+//! `combine_shares` corrects the typed codes of any `threshold` shares and
+//! reconstructs the secret.
+//!
+//! ## Backup sheets
+//!
+//! With the "alloc" feature, `BackupSheet` lays out longer data - like a
+//! recovery key - as numbered lines of codes for printing on paper, as
+//! plain text or Markdown. Each line is corrected separately, and
+//! `parse_backup_sheet` accepts the whole typed sheet back, reporting
+//! which lines couldn't be read or are missing.
+//!
+//! ## Rendering labels
+//!
+//! With the "render" feature, `render_svg` draws a label with a QR code of
+//! the raw code next to the pretty code, in a built-in monospace font that
+//! keeps similar characters apart. The "png" feature adds `render_png`,
+//! which draws the same label as a PNG image. Both the scanned QR code and
+//! the typed code can be passed to `decode_chunk`.
+//!
+//! ## Numeric codes
+//!
+//! `EncodedChunk::numeric` writes a code as decimal digits - 2 digits for
+//! each character - for phone keypads and IVR systems.
+//! `decode_chunk_numeric` corrects numeric codes with the same error
+//! correcting symbols as the other formats, since a mistake in either digit
+//! of a pair only affects a single character.
+//!
+//! ## Check characters
+//!
+//! For very short codes, `CheckDigit` adds a single Damm or Verhoeff check
+//! character instead of Reed-Solomon symbols. Neither can correct errors,
+//! but both detect every single character error and every swap of two
+//! adjacent characters, which 1 Reed-Solomon symbol doesn't.
+//!
+//! ## Interleaved codes
+//!
+//! A single code holds at most 150 bits, since a Reed-Solomon codeword over
+//! GF(2^5) is at most 31 characters long. With the "alloc" feature,
+//! `encode_interleaved` splits longer data across several codewords and
+//! interleaves them character by character into one longer code, so that a
+//! burst of errors - like a smudged group of characters - is spread across
+//! all of the codewords. `decode_interleaved` corrects each codeword and
+//! puts the data back together.
+//!
+//! ## Custom Reed-Solomon parameters
+//!
+//! Codes are compatible with other Reed-Solomon implementations over GF(2^5)
+//! that use the primitive polynomial x^5 + x^2 + 1, the generator 2, and a
+//! first consecutive root of 0. To interoperate with an implementation that
+//! uses different parameters, create an `RsParams` and pass it to
+//! `ChunkEncoder::with_params` or `ChunkDecoder::with_params`. Both are const
+//! fns, so the tables for the code can be computed at compile time.
+//!
+//! ## Runtime tables
+//!
+//! Enabling the "runtime-tables" feature adds `RuntimeChunkEncoder` and
+//! `RuntimeChunkDecoder`, which compute the Reed-Solomon tables at runtime into
+//! a buffer of `RS_TABLES_LEN` bytes provided by the caller instead of using
+//! the precomputed tables. This trades a little RAM for flash on
+//! microcontrollers. The `size_runtime_tables`, `size_builtin` (the tables for
+//! a single ECC level), and `size_builtin_all` (the tables for every level)
+//! examples can be built to compare the binary sizes on a given target.
+//!
+//! ## Multiple profiles
+//!
+//! When codes have been issued with different parameters over time,
+//! `decode_chunk_profiles` takes a list of allowed `(bits, ecc)` profiles and
+//! tries each one that matches the length of the code. A profile that decodes
+//! the code without any corrections is preferred, and the profile that matched
+//! is reported along with the decoded data. If more than one profile decodes
+//! the code equally well, an error is returned instead of guessing.
+//!
+//! ## Serde
+//!
+//! Enabling the "serde" feature implements `Serialize` for the encoded and
+//! decoded chunk types and provides `SerdePrettyCode` for storing byte
+//! payloads as human codes in configuration files.
 //!
 //! ## License
 //!
@@ -63,17 +195,95 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "auth")]
+mod auth;
+#[cfg(all(feature = "rand", feature = "alloc"))]
+mod batch;
+mod bits;
+mod blocklist;
+mod check_digit;
+#[cfg(feature = "alloc")]
+mod codebook;
+#[cfg(feature = "std")]
+mod confirm;
 mod decode_chunk;
 mod encode_chunk;
+#[cfg(feature = "encrypt")]
+mod encrypt;
 mod error;
+mod expiry;
+mod feistel;
+#[cfg(feature = "rand")]
+mod generate;
+#[cfg(feature = "alloc")]
+mod interleave;
+mod numeric;
+mod permute;
+mod profile;
+#[cfg(feature = "render")]
+mod render;
+mod rs;
+#[cfg(feature = "runtime-tables")]
+mod runtime_tables;
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "alloc")]
+mod shamir;
+#[cfg(feature = "alloc")]
+mod sheet;
 mod smallbytebuf;
+#[cfg(feature = "totp")]
+mod totp;
 
 #[cfg(test)]
 mod tests;
 
-pub use decode_chunk::{decode_chunk, DecodedChunk};
-pub use encode_chunk::{encode_chunk, EncodedChunk, EncodedChunkPretty, EncodedChunkRaw};
-pub use error::{HumancodeError, InputErrorCause, UsageErrorCause};
+#[cfg(feature = "auth")]
+pub use auth::CodeAuthenticator;
+#[cfg(all(feature = "rand", feature = "alloc"))]
+pub use batch::generate_batch;
+pub use bits::{BitReader, BitWriter};
+pub use blocklist::{Blocklist, DEFAULT_BLOCKLIST};
+pub use check_digit::CheckDigit;
+#[cfg(feature = "alloc")]
+pub use codebook::{CodeBook, CodeBookMatch};
+#[cfg(feature = "std")]
+pub use confirm::{confirm_correction, decode_chunk_interactive, HighlightStyle};
+pub use decode_chunk::{decode_chunk, parse_chunk, DecodedChunk};
+pub use encode_chunk::{
+    encode_chunk, ChunkFormat, EncodedChunk, EncodedChunkPretty, EncodedChunkRaw,
+};
+#[cfg(feature = "encrypt")]
+pub use encrypt::PayloadCipher;
+pub use error::{HumancodeError, InputErrorCause, InputErrorKind, UsageErrorCause};
+pub use expiry::Expiry;
+#[cfg(feature = "rand")]
+pub use generate::generate;
+#[cfg(feature = "alloc")]
+pub use interleave::{decode_interleaved, encode_interleaved, InterleavedCode};
+pub use numeric::{decode_chunk_numeric, EncodedChunkNumeric};
+pub use permute::KeyedPermutation;
+pub use profile::{decode_chunk_profiles, ProfileMatch};
+#[cfg(feature = "png")]
+pub use render::render_png;
+#[cfg(feature = "render")]
+pub use render::render_svg;
+pub use rs::RsParams;
+#[cfg(feature = "runtime-tables")]
+pub use runtime_tables::{RuntimeChunkDecoder, RuntimeChunkEncoder, RS_TABLES_LEN};
+#[cfg(feature = "serde")]
+pub use serde_support::SerdePrettyCode;
+#[cfg(all(feature = "alloc", feature = "rand"))]
+pub use shamir::split_secret;
+#[cfg(feature = "alloc")]
+pub use shamir::{combine_shares, share_code_bits, ShamirShare};
+#[cfg(feature = "alloc")]
+pub use sheet::{parse_backup_sheet, BackupSheet, LineReport, LineStatus, SheetReport};
+#[cfg(feature = "totp")]
+pub use totp::Totp;
 
 pub mod decoder {
     //! Using the [`ChunkDecoder`] interfaces in this module _may_ allow for a smaller binary size
@@ -96,6 +306,9 @@ pub mod encoder {
     //! since it _may_ allow for certain pre-calculated tables to be removed at
     //! build time. This won't work for all targets and at best can save about 1k
     //! over using [`crate::encode_chunk()`] directly.
+    //!
+    //! To remove the precomputed tables entirely, see the "runtime-tables"
+    //! feature.
     pub use crate::encode_chunk::{
         ChunkEncoder, CHUNK_ENCODER_0, CHUNK_ENCODER_1, CHUNK_ENCODER_10, CHUNK_ENCODER_11,
         CHUNK_ENCODER_12, CHUNK_ENCODER_13, CHUNK_ENCODER_14, CHUNK_ENCODER_15, CHUNK_ENCODER_16,
@@ -0,0 +1,157 @@
+use crate::decode_chunk::{decode_chunk, ChunkDecoder, DecodedChunk};
+use crate::encode_chunk::EncodedChunk;
+use crate::error::{decode_buffer_too_big, HumancodeError};
+use crate::smallbytebuf::SmallByteBuf;
+use core::fmt::{Debug, Display, Formatter};
+use libzbase32::low_level_decode::character_to_quintet;
+use libzbase32::low_level_encode::quintet_to_character;
+
+/// A code in the "numeric" format - every z-base-32 character is written as
+/// 2 decimal digits, so that the code can be entered on a phone keypad.
+///
+/// Each character of the code becomes its own pair of digits - the value of
+/// the character, from "00" to "31". A mistake in either digit of a pair is
+/// a single error in the code, so numeric codes can be corrected exactly like
+/// the other formats with [`decode_chunk_numeric`]. Numeric codes are twice as
+/// long as raw codes.
+#[derive(Copy, Clone)]
+pub struct EncodedChunkNumeric {
+    buf: SmallByteBuf<62>,
+}
+
+impl EncodedChunkNumeric {
+    pub(crate) fn from_raw(raw: &[u8]) -> EncodedChunkNumeric {
+        let mut digits = [0u8; 62];
+        for (pair, &x) in digits.chunks_mut(2).zip(raw.iter()) {
+            let quintet = character_to_quintet(x)
+                .expect("character_to_quintet() failed - which shouldn't be possible since codes only contain valid characters");
+            pair[0] = b'0' + quintet / 10;
+            pair[1] = b'0' + quintet % 10;
+        }
+        EncodedChunkNumeric {
+            buf: SmallByteBuf::new(digits, 2 * raw.len() as u8),
+        }
+    }
+
+    /// Get the code as a `str`
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(self.buf.as_bytes())
+            .expect("Encoded result couldn't be converted to utf-8 - which shouldn't be possible")
+    }
+}
+
+impl PartialEq for EncodedChunkNumeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.as_bytes() == other.buf.as_bytes()
+    }
+}
+
+impl Eq for EncodedChunkNumeric {}
+
+impl PartialEq<str> for EncodedChunkNumeric {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for EncodedChunkNumeric {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl AsRef<str> for EncodedChunkNumeric {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Debug for EncodedChunkNumeric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for EncodedChunkNumeric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ChunkDecoder {
+    /// Decode and correct a code in the "numeric" format - see
+    /// [`decode_chunk_numeric`](crate::decode_chunk_numeric())
+    pub fn decode_chunk_numeric(
+        &self,
+        encoded_data: &str,
+        bits: u8,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        let characters = numeric_to_characters(encoded_data)?;
+        self.decode_chunk(characters_as_str(&characters), bits)
+    }
+}
+
+/// Decode and correct a code in the "numeric" format, as created by
+/// [`EncodedChunk::numeric`].
+///
+/// `ecc` and `bits` have the same meaning as for
+/// [`decode_chunk`](crate::decode_chunk()). Any "-" or space characters in
+/// `encoded_data` are ignored. Any other character that isn't a digit - for
+/// example, "*" on a keypad - marks an unknown digit, and a pair of digits
+/// that is greater than 31 is treated the same way. Like "!" in the other
+/// formats, unknown digits are cheaper to correct than wrong ones.
+///
+/// The corrected code is returned if there was an error in the input - use
+/// [`EncodedChunk::numeric`] to show it to the user.
+pub fn decode_chunk_numeric(
+    encoded_data: &str,
+    ecc: u8,
+    bits: u8,
+) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+    let characters = numeric_to_characters(encoded_data)?;
+    decode_chunk(characters_as_str(&characters), ecc, bits)
+}
+
+// Convert each pair of digits into a z-base-32 character. Pairs that don't
+// map to a character become "!" which the decoder treats as an erasure. An
+// odd digit at the end is kept as an erasure as well, so that the decoder
+// reports the length as wrong.
+fn numeric_to_characters(encoded_data: &str) -> Result<SmallByteBuf<31>, HumancodeError> {
+    let mut characters = [0u8; 31];
+    let mut len = 0;
+    let mut pending: Option<Option<u8>> = None;
+    for x in encoded_data.bytes().filter(|&x| x != b'-' && x != b' ') {
+        let digit = if x.is_ascii_digit() {
+            Some(x - b'0')
+        } else {
+            None
+        };
+        match pending.take() {
+            None => pending = Some(digit),
+            Some(first) => {
+                if len >= characters.len() {
+                    return Err(decode_buffer_too_big());
+                }
+                characters[len] = match (first, digit) {
+                    (Some(a), Some(b)) if a * 10 + b < 32 => quintet_to_character(a * 10 + b)
+                        .expect("quintet_to_character() failed - which shouldn't be possible since the value is less than 32"),
+                    _ => b'!',
+                };
+                len += 1;
+            }
+        }
+    }
+    if pending.is_some() {
+        if len >= characters.len() {
+            return Err(decode_buffer_too_big());
+        }
+        characters[len] = b'!';
+        len += 1;
+    }
+    Ok(SmallByteBuf::new(characters, len as u8))
+}
+
+fn characters_as_str(characters: &SmallByteBuf<31>) -> &str {
+    core::str::from_utf8(characters.as_bytes())
+        .expect("Converted characters aren't valid utf-8 - which shouldn't be possible")
+}
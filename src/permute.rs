@@ -0,0 +1,141 @@
+use crate::decode_chunk::DecodedChunk;
+use crate::encode_chunk::EncodedChunk;
+use crate::error::HumancodeError;
+use crate::feistel::{
+    decode_chunk_permuted, encode_chunk_permuted, permute, unpermute, RoundFunction,
+};
+use core::convert::TryInto;
+
+/// A `KeyedPermutation` shuffles all of the possible values of a given
+/// number of bits, based on a secret key.
+///
+/// Each key gives a different permutation, so sequential values - for
+/// example, database IDs - come out looking unrelated to each other, but the
+/// original value can always be recovered with the same key. The size of
+/// the value doesn't change, so a permuted value can be encoded into a code
+/// of exactly the same length as the original value.
+///
+/// The permutation is a Feistel network over exactly `bits` bits with
+/// SipHash-2-4 as the round function. This hides the order of values and
+/// makes it impractical to guess valid codes without the key, but it
+/// isn't meant to be used as general purpose encryption.
+#[derive(Clone)]
+pub struct KeyedPermutation {
+    k0: u64,
+    k1: u64,
+}
+
+impl KeyedPermutation {
+    /// Create a new `KeyedPermutation` from a 128-bit secret key
+    pub const fn new(key: &[u8; 16]) -> KeyedPermutation {
+        KeyedPermutation {
+            k0: u64::from_le_bytes([
+                key[0], key[1], key[2], key[3], key[4], key[5], key[6], key[7],
+            ]),
+            k1: u64::from_le_bytes([
+                key[8], key[9], key[10], key[11], key[12], key[13], key[14], key[15],
+            ]),
+        }
+    }
+
+    /// Permute `data` in place.
+    ///
+    /// `data` and `bits` must be valid parameters for
+    /// [`encode_chunk`](crate::encode_chunk()) - so any unused bits in the
+    /// last byte of `data` must be 0.
+    pub fn permute(&self, data: &mut [u8], bits: u8) -> Result<(), HumancodeError> {
+        permute(self, data, bits)
+    }
+
+    /// Reverse [`permute`](KeyedPermutation::permute) in place.
+    pub fn unpermute(&self, data: &mut [u8], bits: u8) -> Result<(), HumancodeError> {
+        unpermute(self, data, bits)
+    }
+
+    /// Permute `data` and then encode it - see [`encode_chunk`](crate::encode_chunk())
+    pub fn encode_chunk(
+        &self,
+        data: &[u8],
+        ecc: u8,
+        bits: u8,
+    ) -> Result<EncodedChunk, HumancodeError> {
+        encode_chunk_permuted(self, data, ecc, bits)
+    }
+
+    /// Decode a code and then reverse the permutation - see
+    /// [`decode_chunk`](crate::decode_chunk())
+    pub fn decode_chunk(
+        &self,
+        encoded_data: &str,
+        ecc: u8,
+        bits: u8,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        decode_chunk_permuted(self, encoded_data, ecc, bits)
+    }
+}
+
+impl RoundFunction for KeyedPermutation {
+    fn round(&self, bits: u8, round: u8, value: u128) -> u128 {
+        // Each half is at most 75 bits, so we need 2 SipHash outputs
+        let mut message = [0u8; 19];
+        message[0] = bits;
+        message[1] = round;
+        message[3..].copy_from_slice(&value.to_le_bytes());
+        let low = siphash_2_4(self.k0, self.k1, &message);
+        message[2] = 1;
+        let high = siphash_2_4(self.k0, self.k1, &message);
+        ((high as u128) << 64) | low as u128
+    }
+}
+
+impl core::fmt::Debug for KeyedPermutation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Don't leak the key into logs
+        f.debug_struct("KeyedPermutation").finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn siphash_2_4(k0: u64, k1: u64, message: &[u8]) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+
+    fn sip_round(v: &mut [u64; 4]) {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+
+    let mut blocks = message.chunks_exact(8);
+    for block in &mut blocks {
+        let m = u64::from_le_bytes(block.try_into().unwrap());
+        v[3] ^= m;
+        sip_round(&mut v);
+        sip_round(&mut v);
+        v[0] ^= m;
+    }
+    let mut last = [0u8; 8];
+    last[..blocks.remainder().len()].copy_from_slice(blocks.remainder());
+    last[7] = message.len() as u8;
+    let m = u64::from_le_bytes(last);
+    v[3] ^= m;
+    sip_round(&mut v);
+    sip_round(&mut v);
+    v[0] ^= m;
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
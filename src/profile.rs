@@ -0,0 +1,120 @@
+use crate::decode_chunk::{decode_chunk, DecodedChunk};
+use crate::encode_chunk::EncodedChunk;
+use crate::error::{
+    ambiguous_profile, invalid_bits, invalid_ecc_len, no_matching_profile, HumancodeError,
+};
+use libzbase32::low_level_encode::required_quintets_buffer_len;
+
+/// The result of decoding a code with [`decode_chunk_profiles`]
+#[derive(Copy, Clone, Debug)]
+pub struct ProfileMatch {
+    bits: u8,
+    ecc: u8,
+    decoded: DecodedChunk,
+    corrected: Option<EncodedChunk>,
+}
+
+impl ProfileMatch {
+    /// Get the number of bits of the profile that matched
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Get the number of error correcting symbols of the profile that matched
+    pub fn ecc(&self) -> u8 {
+        self.ecc
+    }
+
+    /// Get the decoded data
+    pub fn decoded(&self) -> &DecodedChunk {
+        &self.decoded
+    }
+
+    /// Get the corrected code - this is only `Some` if there was an error in
+    /// the input that was corrected
+    pub fn corrected(&self) -> Option<&EncodedChunk> {
+        self.corrected.as_ref()
+    }
+
+    /// Get the decoded data and the corrected code, in the same form as
+    /// returned by [`decode_chunk`](crate::decode_chunk())
+    pub fn into_parts(self) -> (DecodedChunk, Option<EncodedChunk>) {
+        (self.decoded, self.corrected)
+    }
+}
+
+/// Decode and correct a code that may have been created with any of several
+/// `(bits, ecc)` profiles.
+///
+/// This is useful when codes have been issued with different parameters over
+/// time - for example, older codes with 4 error correcting symbols and newer
+/// ones with 6. Only the profiles that match the length of `encoded_data`,
+/// ignoring any "-" characters, are tried. A profile that decodes the code
+/// without any corrections is preferred over one that had to correct errors.
+///
+/// If the length doesn't match any of the profiles, or if more than one
+/// profile decodes the code equally well, an
+/// [`InputError`](crate::HumancodeError::InputError) is returned. Note that
+/// a valid code is also valid for any profile with the same length and fewer
+/// error correcting symbols, so profiles of the same length should generally
+/// be avoided. If none of the matching profiles can decode the code, the
+/// error from the first one is returned.
+pub fn decode_chunk_profiles(
+    encoded_data: &str,
+    profiles: &[(u8, u8)],
+) -> Result<ProfileMatch, HumancodeError> {
+    let len = encoded_data.bytes().filter(|&x| x != b'-').count();
+
+    let mut best: Option<ProfileMatch> = None;
+    let mut ambiguous = false;
+    let mut first_err = None;
+    let mut any_matched = false;
+    for (i, &(bits, ecc)) in profiles.iter().enumerate() {
+        if bits == 0 || bits > 150 {
+            return Err(invalid_bits());
+        }
+        if ecc > 30 {
+            return Err(invalid_ecc_len());
+        }
+        let data_len = required_quintets_buffer_len(bits as u64)
+            .expect("required_quintets_buffer_len() failed - which shouldn't be possible");
+        // Duplicate profiles would otherwise always be ambiguous
+        if data_len + ecc as usize != len || profiles[..i].contains(&(bits, ecc)) {
+            continue;
+        }
+        any_matched = true;
+
+        let (decoded, corrected) = match decode_chunk(encoded_data, ecc, bits) {
+            Ok(result) => result,
+            Err(err) => {
+                first_err.get_or_insert(err);
+                continue;
+            }
+        };
+        let candidate = ProfileMatch {
+            bits,
+            ecc,
+            decoded,
+            corrected,
+        };
+        match &best {
+            None => best = Some(candidate),
+            Some(existing) => match (existing.corrected.is_some(), corrected.is_some()) {
+                (true, false) => {
+                    best = Some(candidate);
+                    ambiguous = false;
+                }
+                (false, true) => {}
+                _ => ambiguous = true,
+            },
+        }
+    }
+
+    match best {
+        Some(_) if ambiguous => Err(ambiguous_profile()),
+        Some(best) => Ok(best),
+        None if !any_matched => Err(no_matching_profile()),
+        None => Err(first_err
+            .expect("No error was recorded - which shouldn't be possible since a profile matched")),
+    }
+}
@@ -0,0 +1,355 @@
+use crate::encode_chunk::EncodedChunk;
+#[cfg(feature = "png")]
+use crate::error::{invalid_render_scale, HumancodeError};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use qrcode::{Color, QrCode};
+
+// All sizes are in QR code modules
+const QUIET_ZONE: usize = 4;
+const GLYPH_HEIGHT: usize = 9;
+const GLYPH_ADVANCE: usize = 6;
+
+// A 5x9 bitmap font for the z-base-32 alphabet and "-". Each row is 5 bits,
+// with the highest bit on the left. The glyphs are drawn so that characters
+// which are easy to mix up - like "1", "i", and "7" or "g", "q", and "y" -
+// look clearly different, no matter which fonts are installed. For example,
+// the tails of "g" and "y" hook back to the left, while "q" has a narrower
+// bowl and a tail that kicks out to the right.
+static GLYPHS: [(u8, [u8; GLYPH_HEIGHT]); 33] = [
+    (
+        b'a',
+        [
+            0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'b',
+        [
+            0b10000, 0b10000, 0b11110, 0b10001, 0b10001, 0b10001, 0b11110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'c',
+        [
+            0b00000, 0b00000, 0b01111, 0b10000, 0b10000, 0b10000, 0b01111, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'd',
+        [
+            0b00001, 0b00001, 0b01111, 0b10001, 0b10001, 0b10001, 0b01111, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'e',
+        [
+            0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'f',
+        [
+            0b00110, 0b01000, 0b11110, 0b01000, 0b01000, 0b01000, 0b01000, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'g',
+        [
+            0b00000, 0b00000, 0b01111, 0b10001, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110,
+        ],
+    ),
+    (
+        b'h',
+        [
+            0b10000, 0b10000, 0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'i',
+        [
+            0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'j',
+        [
+            0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+    ),
+    (
+        b'k',
+        [
+            0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'm',
+        [
+            0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10101, 0b10101, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'n',
+        [
+            0b00000, 0b00000, 0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'o',
+        [
+            0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'p',
+        [
+            0b00000, 0b00000, 0b11110, 0b10001, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000,
+        ],
+    ),
+    (
+        b'q',
+        [
+            0b00000, 0b00000, 0b01110, 0b10010, 0b10010, 0b10010, 0b01110, 0b00010, 0b00011,
+        ],
+    ),
+    (
+        b'r',
+        [
+            0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b's',
+        [
+            0b00000, 0b00000, 0b01111, 0b10000, 0b01110, 0b00001, 0b11110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b't',
+        [
+            0b01000, 0b01000, 0b11110, 0b01000, 0b01000, 0b01000, 0b00110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'u',
+        [
+            0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10001, 0b01111, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'w',
+        [
+            0b00000, 0b00000, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'x',
+        [
+            0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'y',
+        [
+            0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110,
+        ],
+    ),
+    (
+        b'z',
+        [
+            0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'1',
+        [
+            0b00100, 0b01100, 0b10100, 0b00100, 0b00100, 0b00100, 0b11111, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'3',
+        [
+            0b11110, 0b00001, 0b00001, 0b01110, 0b00001, 0b00001, 0b11110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'4',
+        [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'5',
+        [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'6',
+        [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'7',
+        [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'8',
+        [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'9',
+        [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100, 0b00000, 0b00000,
+        ],
+    ),
+    (
+        b'-',
+        [
+            0b00000, 0b00000, 0b00000, 0b00000, 0b01110, 0b00000, 0b00000, 0b00000, 0b00000,
+        ],
+    ),
+];
+
+/// Render a code as an SVG image for printing on a label.
+///
+/// The image has a QR code of the raw form of the code on the left and the
+/// pretty form of the code on the right, drawn with a built-in monospace
+/// font which keeps similar characters apart. Both the scanned QR code and
+/// the typed code can be passed to [`decode_chunk`](crate::decode_chunk()).
+///
+/// The image is sized in QR code modules, so it should be scaled up when it
+/// is displayed.
+pub fn render_svg(code: EncodedChunk) -> String {
+    let label = Label::new(code);
+    let mut out = String::new();
+    write!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\" role=\"img\" aria-label=\"{code}\">\n<title>{code}</title>\n<rect width=\"{w}\" height=\"{h}\" fill=\"#fff\"/>\n<path fill=\"#000\" d=\"",
+        w = label.width,
+        h = label.height,
+        code = code.pretty(),
+    )
+    .expect("Writing to a String can't fail");
+
+    // Draw each horizontal run of dark modules as a single rectangle
+    for y in 0..label.height {
+        let row = &label.pixels[y * label.width..(y + 1) * label.width];
+        let mut x = 0;
+        while x < label.width {
+            if !row[x] {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < label.width && row[x] {
+                x += 1;
+            }
+            write!(out, "M{} {}h{}v1h-{}z", start, y, x - start, x - start)
+                .expect("Writing to a String can't fail");
+        }
+    }
+    out.push_str("\"/>\n</svg>\n");
+    out
+}
+
+/// Render a code as a grayscale PNG image for printing on a label.
+///
+/// The layout is the same as for [`render_svg`]. Each QR code module is
+/// drawn as a `scale` by `scale` square of pixels - `scale` must be in the
+/// range [1,64].
+#[cfg(feature = "png")]
+pub fn render_png(code: EncodedChunk, scale: u32) -> Result<Vec<u8>, HumancodeError> {
+    if scale == 0 || scale > 64 {
+        return Err(invalid_render_scale());
+    }
+    let label = Label::new(code);
+    let scale = scale as usize;
+    let width = label.width * scale;
+    let height = label.height * scale;
+
+    let mut image = vec![0xffu8; width * height];
+    for (y, row) in image.chunks_mut(width).enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            if label.pixels[(y / scale) * label.width + x / scale] {
+                *pixel = 0;
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .expect("write_header() failed - which shouldn't be possible when writing to a Vec");
+    writer
+        .write_image_data(&image)
+        .expect("write_image_data() failed - which shouldn't be possible when writing to a Vec");
+    writer
+        .finish()
+        .expect("finish() failed - which shouldn't be possible when writing to a Vec");
+    Ok(out)
+}
+
+// The label as a grid of modules, where true is dark
+struct Label {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Label {
+    fn new(code: EncodedChunk) -> Label {
+        let qr = QrCode::new(code.raw().as_str())
+            .expect("QrCode::new() failed - which shouldn't be possible since codes are at most 31 characters");
+        let qr_width = qr.width();
+        let pretty = code.pretty();
+        let text = pretty.as_str().as_bytes();
+
+        let width =
+            QUIET_ZONE + qr_width + QUIET_ZONE + text.len() * GLYPH_ADVANCE - 1 + QUIET_ZONE;
+        let height = qr_width + 2 * QUIET_ZONE;
+        let mut label = Label {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        };
+
+        for (i, color) in qr.to_colors().into_iter().enumerate() {
+            if color == Color::Dark {
+                label.set(QUIET_ZONE + i % qr_width, QUIET_ZONE + i / qr_width);
+            }
+        }
+
+        let left = 2 * QUIET_ZONE + qr_width;
+        let top = (height - GLYPH_HEIGHT) / 2;
+        for (i, c) in text.iter().enumerate() {
+            let (_, glyph) = GLYPHS
+                .iter()
+                .find(|(x, _)| x == c)
+                .expect("Missing glyph - which shouldn't be possible since codes only contain z-base-32 characters and \"-\"");
+            for (y, row) in glyph.iter().enumerate() {
+                for x in 0..5 {
+                    if row & (0b10000 >> x) != 0 {
+                        label.set(left + i * GLYPH_ADVANCE + x, top + y);
+                    }
+                }
+            }
+        }
+        label
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        self.pixels[y * self.width + x] = true;
+    }
+}
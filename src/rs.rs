@@ -0,0 +1,427 @@
+use crate::error::{invalid_ecc_len, invalid_rs_params, HumancodeError};
+use crate::smallbytebuf::SmallByteBuf;
+
+/// The parameters of a Reed-Solomon code over GF(2^5).
+///
+/// These only need to be changed to interoperate with another
+/// implementation that uses different parameters - codes created with
+/// different parameters aren't compatible with each other. The
+/// [`CHUNK_ENCODER_N`](crate::encoder) and
+/// [`CHUNK_DECODER_N`](crate::decoder) constants, as well as
+/// [`encode_chunk`](crate::encode_chunk()) and
+/// [`decode_chunk`](crate::decode_chunk()), always use
+/// [`RsParams::DEFAULT`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RsParams {
+    primitive_poly: u8,
+    generator: u8,
+    fcr: u8,
+}
+
+impl RsParams {
+    /// The parameters used by the rest of this crate: the primitive polynomial
+    /// x^5 + x^2 + 1 (`0x25`), the generator 2, and a first consecutive root
+    /// of 0.
+    pub const DEFAULT: RsParams = RsParams {
+        primitive_poly: 0x25,
+        generator: 2,
+        fcr: 0,
+    };
+
+    /// Create a new set of parameters.
+    ///
+    /// `primitive_poly` is the field's polynomial, including the x^5 term -
+    /// for example, `0x25` for x^5 + x^2 + 1. It must be irreducible.
+    /// `generator` is the element whose powers are the roots of the code, and
+    /// it must not be 0 or 1. The roots are `generator^fcr`,
+    /// `generator^(fcr + 1)`, and so on, with `fcr` in the range [0,30].
+    ///
+    /// These have the same meaning as the `prim`, `generator`, and `fcr`
+    /// parameters of the Python `reedsolo` package with `c_exp=5`.
+    pub const fn new(
+        primitive_poly: u8,
+        generator: u8,
+        fcr: u8,
+    ) -> Result<RsParams, HumancodeError> {
+        if primitive_poly < 0x20
+            || primitive_poly > 0x3f
+            || generator < 2
+            || generator > 31
+            || fcr > 30
+        {
+            return Err(invalid_rs_params());
+        }
+        // Every element other than 0 and 1 generates the whole multiplicative
+        // group, since it has a prime order - but only if the polynomial is
+        // irreducible, which is checked by making sure that the powers of the
+        // generator don't repeat too early.
+        let mut x = 1;
+        let mut i = 1;
+        while i < 31 {
+            x = gf_mul_slow(x, generator, primitive_poly);
+            if x == 1 || x == 0 {
+                return Err(invalid_rs_params());
+            }
+            i += 1;
+        }
+        if gf_mul_slow(x, generator, primitive_poly) != 1 {
+            return Err(invalid_rs_params());
+        }
+        Ok(RsParams {
+            primitive_poly,
+            generator,
+            fcr,
+        })
+    }
+
+    /// Get the primitive polynomial
+    pub const fn primitive_poly(&self) -> u8 {
+        self.primitive_poly
+    }
+
+    /// Get the generator
+    pub const fn generator(&self) -> u8 {
+        self.generator
+    }
+
+    /// Get the first consecutive root
+    pub const fn fcr(&self) -> u8 {
+        self.fcr
+    }
+}
+
+// The number of bytes needed to hold the tables for a code: exp[i] is
+// generator^i and is repeated so that the sum of two logs can be used as an
+// index without reducing it (62 bytes), followed by the logs (32 bytes), and
+// the generator polynomial (31 bytes).
+#[cfg(feature = "runtime-tables")]
+pub(crate) const TABLES_LEN: usize = 62 + 32 + 31;
+
+// A Reed-Solomon encoder and decoder for custom parameters, with all of its
+// tables computed when it is created - which may be at compile time, since
+// creating one is a const fn.
+#[derive(Debug)]
+pub(crate) struct RsCodec {
+    ecc: u8,
+    fcr: u8,
+    exp: [u8; 62],
+    log: [u8; 32],
+    gen_poly: [u8; 31],
+}
+
+impl RsCodec {
+    pub(crate) const fn new(params: RsParams, ecc: u8) -> Result<RsCodec, HumancodeError> {
+        if ecc > 30 {
+            return Err(invalid_ecc_len());
+        }
+        let mut exp = [0u8; 62];
+        let mut log = [0u8; 32];
+        let mut gen_poly = [0u8; 31];
+        fill_tables(params, ecc, &mut exp, &mut log, &mut gen_poly);
+        Ok(RsCodec {
+            ecc,
+            fcr: params.fcr,
+            exp,
+            log,
+            gen_poly,
+        })
+    }
+
+    pub(crate) fn tables(&self) -> RsTables<'_> {
+        RsTables {
+            ecc: self.ecc,
+            fcr: self.fcr,
+            exp: &self.exp,
+            log: &self.log,
+            gen_poly: &self.gen_poly,
+        }
+    }
+}
+
+// The tables for a code, which may be owned by an RsCodec or stored in a
+// buffer provided by the caller. All of the encoding and decoding is done
+// through this type so that the code is shared between both.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct RsTables<'a> {
+    ecc: u8,
+    fcr: u8,
+    exp: &'a [u8; 62],
+    log: &'a [u8; 32],
+    // The generator polynomial, highest degree first, with gen_poly[0] = 1
+    gen_poly: &'a [u8; 31],
+}
+
+impl<'a> RsTables<'a> {
+    // Compute the tables into `buf` at runtime
+    #[cfg(feature = "runtime-tables")]
+    pub(crate) fn compute(
+        buf: &'a mut [u8; TABLES_LEN],
+        params: RsParams,
+        ecc: u8,
+    ) -> Result<RsTables<'a>, HumancodeError> {
+        use core::convert::TryInto;
+
+        if ecc > 30 {
+            return Err(invalid_ecc_len());
+        }
+        let (exp, rest) = buf.split_at_mut(62);
+        let (log, gen_poly) = rest.split_at_mut(32);
+        let exp: &'a mut [u8; 62] = exp.try_into().expect("exp table has the wrong length");
+        let log: &'a mut [u8; 32] = log.try_into().expect("log table has the wrong length");
+        let gen_poly: &'a mut [u8; 31] = gen_poly
+            .try_into()
+            .expect("generator polynomial has the wrong length");
+        fill_tables(params, ecc, exp, log, gen_poly);
+        Ok(RsTables {
+            ecc,
+            fcr: params.fcr,
+            exp,
+            log,
+            gen_poly,
+        })
+    }
+
+    #[cfg(feature = "runtime-tables")]
+    pub(crate) fn ecc(&self) -> u8 {
+        self.ecc
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        gf_mul(self.exp, self.log, a, b)
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        if a == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + 31 - self.log[b as usize] as usize]
+        }
+    }
+
+    fn pow(&self, e: usize) -> u8 {
+        self.exp[e % 31]
+    }
+
+    fn eval(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter().fold(0, |y, &c| self.mul(y, x) ^ c)
+    }
+
+    // Encode the data, returning the data followed by the ecc symbols. The
+    // caller must make sure that the total length is no more than 31.
+    pub(crate) fn encode(&self, data: &[u8]) -> SmallByteBuf<31> {
+        let ecc = self.ecc as usize;
+        let len = data.len() + ecc;
+        assert!(len <= 31);
+
+        let mut buf = [0u8; 31];
+        buf[..data.len()].copy_from_slice(data);
+        // Polynomial long division - the remainder is left in the ecc symbols
+        let mut remainder = buf;
+        for i in 0..data.len() {
+            let coef = remainder[i];
+            if coef != 0 {
+                for j in 1..=ecc {
+                    remainder[i + j] ^= self.mul(self.gen_poly[j], coef);
+                }
+            }
+        }
+        buf[data.len()..len].copy_from_slice(&remainder[data.len()..len]);
+        SmallByteBuf::new(buf, len as u8)
+    }
+
+    // Correct a message, returning the corrected message and the number of
+    // symbols that were corrected. `erase_pos` are the positions of symbols
+    // that are known to be wrong. Errors are found with Berlekamp-Massey and
+    // corrected with Forney's algorithm.
+    pub(crate) fn correct(
+        &self,
+        msg: &[u8],
+        erase_pos: &[u8],
+    ) -> Result<(SmallByteBuf<31>, usize), ()> {
+        let ecc = self.ecc as usize;
+        let n = msg.len();
+        assert!(n <= 31);
+        // Each erasure uses up one ecc symbol, even if the erased symbol
+        // happens to be correct
+        if erase_pos.len() > ecc {
+            return Err(());
+        }
+        let mut buf = [0u8; 31];
+        buf[..n].copy_from_slice(msg);
+        let msg = &mut buf[..n];
+        for &pos in erase_pos.iter() {
+            msg[pos as usize] = 0;
+        }
+
+        let mut syndromes = [0u8; 30];
+        for (i, s) in syndromes[..ecc].iter_mut().enumerate() {
+            *s = self.eval(msg, self.pow(self.fcr as usize + i));
+        }
+        if syndromes.iter().all(|&s| s == 0) {
+            return Ok((SmallByteBuf::new(buf, n as u8), erase_pos.len()));
+        }
+
+        // All polynomials below are lowest degree first. Start with the
+        // erasure locator polynomial.
+        let mut erasures = [0u8; 32];
+        erasures[0] = 1;
+        for &pos in erase_pos.iter() {
+            let x = self.pow(n - 1 - pos as usize);
+            for j in (1..32).rev() {
+                erasures[j] ^= self.mul(erasures[j - 1], x);
+            }
+        }
+
+        let mut locator = erasures;
+        let mut prev = erasures;
+        let mut len = erase_pos.len();
+        for r in erase_pos.len()..ecc {
+            let mut delta = 0;
+            for j in 0..=len.min(r) {
+                delta ^= self.mul(locator[j], syndromes[r - j]);
+            }
+            prev.copy_within(0..31, 1);
+            prev[0] = 0;
+            if delta != 0 {
+                let mut next = locator;
+                for (x, &p) in next.iter_mut().zip(prev.iter()) {
+                    *x ^= self.mul(delta, p);
+                }
+                if 2 * len <= r + erase_pos.len() {
+                    for (p, &l) in prev.iter_mut().zip(locator.iter()) {
+                        *p = self.div(l, delta);
+                    }
+                    len = r + 1 + erase_pos.len() - len;
+                }
+                locator = next;
+            }
+        }
+        let degree = (0..32).rev().find(|&j| locator[j] != 0).unwrap_or(0);
+        if degree != len || degree < erase_pos.len() {
+            return Err(());
+        }
+        // Every error costs 2 ecc symbols and every erasure costs 1 - any more
+        // than that and the locator may point at the wrong symbols
+        let errors = degree - erase_pos.len();
+        if 2 * errors + erase_pos.len() > ecc {
+            return Err(());
+        }
+
+        // Find the roots of the locator by trying every position
+        let mut positions = [0usize; 31];
+        let mut found = 0;
+        for pos in 0..n {
+            let x_inv = self.div(1, self.pow(n - 1 - pos));
+            if self.eval_low_first(&locator[..=degree], x_inv) == 0 {
+                positions[found] = pos;
+                found += 1;
+            }
+        }
+        if found != degree {
+            return Err(());
+        }
+
+        // The error evaluator polynomial, modulo x^ecc
+        let mut evaluator = [0u8; 30];
+        for (i, e) in evaluator[..ecc].iter_mut().enumerate() {
+            for j in 0..=i.min(degree) {
+                *e ^= self.mul(syndromes[i - j], locator[j]);
+            }
+        }
+
+        for &pos in positions[..found].iter() {
+            let x = self.pow(n - 1 - pos);
+            let x_inv = self.div(1, x);
+            let numerator = self.eval_low_first(&evaluator[..ecc], x_inv);
+            // The formal derivative of the locator only has the odd terms
+            let mut denominator = 0;
+            let mut x_pow = 1;
+            for (j, &l) in locator[1..=degree].iter().enumerate() {
+                if j % 2 == 0 {
+                    denominator ^= self.mul(l, x_pow);
+                }
+                x_pow = self.mul(x_pow, x_inv);
+            }
+            if denominator == 0 {
+                return Err(());
+            }
+            // Adjust for the first consecutive root: X^(1 - fcr)
+            let adjust = self.pow(self.log[x as usize] as usize * (32 - self.fcr as usize));
+            msg[pos] ^= self.mul(self.div(numerator, denominator), adjust);
+        }
+
+        for i in 0..ecc {
+            if self.eval(msg, self.pow(self.fcr as usize + i)) != 0 {
+                return Err(());
+            }
+        }
+        Ok((SmallByteBuf::new(buf, n as u8), found))
+    }
+
+    fn eval_low_first(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter().rev().fold(0, |y, &c| self.mul(y, x) ^ c)
+    }
+}
+
+// Fill in the tables for a code. The buffers may hold old values, so every
+// entry is written.
+const fn fill_tables(
+    params: RsParams,
+    ecc: u8,
+    exp: &mut [u8; 62],
+    log: &mut [u8; 32],
+    gen_poly: &mut [u8; 31],
+) {
+    log[0] = 0;
+    let mut x = 1;
+    let mut i = 0;
+    while i < 31 {
+        exp[i] = x;
+        exp[i + 31] = x;
+        log[x as usize] = i as u8;
+        x = gf_mul_slow(x, params.generator, params.primitive_poly);
+        i += 1;
+    }
+
+    // Multiply together (x - root) for each of the roots
+    let mut i = 0;
+    while i < 31 {
+        gen_poly[i] = 0;
+        i += 1;
+    }
+    gen_poly[0] = 1;
+    let mut i = 0;
+    while i < ecc as usize {
+        let root = exp[(params.fcr as usize + i) % 31];
+        let mut j = i + 1;
+        while j > 0 {
+            gen_poly[j] ^= gf_mul(exp, log, gen_poly[j - 1], root);
+            j -= 1;
+        }
+        i += 1;
+    }
+}
+
+const fn gf_mul(exp: &[u8; 62], log: &[u8; 32], a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        exp[log[a as usize] as usize + log[b as usize] as usize]
+    }
+}
+
+// Multiplication without any tables, reducing by the polynomial as we go
+const fn gf_mul_slow(mut a: u8, mut b: u8, poly: u8) -> u8 {
+    let mut result = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            result ^= a;
+        }
+        b >>= 1;
+        a <<= 1;
+        if a & 0x20 != 0 {
+            a ^= poly;
+        }
+    }
+    result
+}
@@ -0,0 +1,116 @@
+use crate::decode_chunk::{decode_with, DecodedChunk};
+use crate::encode_chunk::{data_quintets, EncodedChunk};
+use crate::error::{too_many_errors, HumancodeError};
+use crate::rs::{RsParams, RsTables, TABLES_LEN};
+
+/// The number of bytes needed for the buffer passed to
+/// [`RuntimeChunkEncoder::new`] and [`RuntimeChunkDecoder::new`]
+pub const RS_TABLES_LEN: usize = TABLES_LEN;
+
+/// A `RuntimeChunkEncoder` is like a
+/// [`ChunkEncoder`](crate::encoder::ChunkEncoder), but it computes its
+/// Reed-Solomon tables at runtime into a buffer provided by the caller.
+///
+/// None of the precomputed tables are used, so the only cost in flash is the
+/// code itself. This is meant for microcontrollers where flash is tight, but
+/// [`RS_TABLES_LEN`] bytes of RAM can be spared. Codes are the same as the
+/// ones created by [`encode_chunk`](crate::encode_chunk()).
+#[derive(Copy, Clone, Debug)]
+pub struct RuntimeChunkEncoder<'a> {
+    tables: RsTables<'a>,
+}
+
+impl<'a> RuntimeChunkEncoder<'a> {
+    /// Compute the tables for `ecc` error correcting symbols into `buf`.
+    ///
+    /// `ecc` must be between 0 and 30, inclusive. Any previous contents of
+    /// `buf` are overwritten.
+    pub fn new(
+        buf: &'a mut [u8; RS_TABLES_LEN],
+        ecc: u8,
+    ) -> Result<RuntimeChunkEncoder<'a>, HumancodeError> {
+        RuntimeChunkEncoder::with_params(buf, RsParams::DEFAULT, ecc)
+    }
+
+    /// Compute the tables for custom Reed-Solomon parameters into `buf` - see
+    /// [`ChunkEncoder::with_params`](crate::encoder::ChunkEncoder::with_params).
+    pub fn with_params(
+        buf: &'a mut [u8; RS_TABLES_LEN],
+        params: RsParams,
+        ecc: u8,
+    ) -> Result<RuntimeChunkEncoder<'a>, HumancodeError> {
+        Ok(RuntimeChunkEncoder {
+            tables: RsTables::compute(buf, params, ecc)?,
+        })
+    }
+
+    /// Get a decoder that shares the tables of this encoder, so that only
+    /// one buffer is needed to both encode and decode.
+    pub fn decoder(&self) -> RuntimeChunkDecoder<'a> {
+        RuntimeChunkDecoder {
+            tables: self.tables,
+        }
+    }
+
+    /// Encode a chunk of input data - see
+    /// [`ChunkEncoder::encode_chunk`](crate::encoder::ChunkEncoder::encode_chunk)
+    pub fn encode_chunk(&self, data: &[u8], bits: u8) -> Result<EncodedChunk, HumancodeError> {
+        let quintets_buffer = data_quintets(data, bits, self.tables.ecc())?;
+        Ok(EncodedChunk::from_quintet_buffer(
+            self.tables.encode(quintets_buffer.as_bytes()).as_bytes(),
+        ))
+    }
+}
+
+/// A `RuntimeChunkDecoder` is like a
+/// [`ChunkDecoder`](crate::decoder::ChunkDecoder), but it computes its
+/// Reed-Solomon tables at runtime into a buffer provided by the caller - see
+/// [`RuntimeChunkEncoder`].
+#[derive(Copy, Clone, Debug)]
+pub struct RuntimeChunkDecoder<'a> {
+    tables: RsTables<'a>,
+}
+
+impl<'a> RuntimeChunkDecoder<'a> {
+    /// Compute the tables for `ecc` error correcting symbols into `buf`.
+    ///
+    /// `ecc` must be between 0 and 30, inclusive. Any previous contents of
+    /// `buf` are overwritten.
+    pub fn new(
+        buf: &'a mut [u8; RS_TABLES_LEN],
+        ecc: u8,
+    ) -> Result<RuntimeChunkDecoder<'a>, HumancodeError> {
+        RuntimeChunkDecoder::with_params(buf, RsParams::DEFAULT, ecc)
+    }
+
+    /// Compute the tables for custom Reed-Solomon parameters into `buf` - see
+    /// [`ChunkDecoder::with_params`](crate::decoder::ChunkDecoder::with_params).
+    pub fn with_params(
+        buf: &'a mut [u8; RS_TABLES_LEN],
+        params: RsParams,
+        ecc: u8,
+    ) -> Result<RuntimeChunkDecoder<'a>, HumancodeError> {
+        Ok(RuntimeChunkDecoder {
+            tables: RsTables::compute(buf, params, ecc)?,
+        })
+    }
+
+    /// Decode and correct an encoded message - see
+    /// [`ChunkDecoder::decode_chunk`](crate::decoder::ChunkDecoder::decode_chunk)
+    pub fn decode_chunk(
+        &self,
+        encoded_data: &str,
+        bits: u8,
+    ) -> Result<(DecodedChunk, Option<EncodedChunk>), HumancodeError> {
+        decode_with(
+            encoded_data,
+            bits,
+            self.tables.ecc(),
+            |quintets, erase_pos| {
+                self.tables
+                    .correct(quintets, erase_pos)
+                    .map_err(|_| too_many_errors())
+            },
+        )
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{decode_chunk, encode_chunk, DecodedChunk, EncodedChunkPretty, EncodedChunkRaw};
+use core::convert::TryFrom;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use serde::de::{Error as DeError, Visitor};
+use serde::ser::Error as SerError;
+use serde::{Deserializer, Serialize, Serializer};
+
+impl Serialize for EncodedChunkRaw {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Serialize for EncodedChunkPretty {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Serialize for DecodedChunk {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
+/// A helper for use with `#[serde(with = ...)]` that stores a byte payload
+/// as a human code.
+///
+/// `ECC` and `BITS` have the same meaning as the corresponding parameters
+/// of [`encode_chunk`](crate::encode_chunk()). The payload is serialized
+/// in the "pretty" format. On deserialize, either the "raw" or the "pretty"
+/// format is accepted and errors are corrected where possible.
+///
+/// The field type must implement `AsRef<[u8]>` to be serialized and
+/// `TryFrom<&[u8]>` to be deserialized - for example, `[u8; 16]`.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "libhumancode::SerdePrettyCode::<5, 128>")]
+///     key: [u8; 16],
+/// }
+/// ```
+pub struct SerdePrettyCode<const ECC: u8, const BITS: u8> {
+    __hidden: (),
+}
+
+impl<const ECC: u8, const BITS: u8> SerdePrettyCode<ECC, BITS> {
+    /// Serialize `value` as a "pretty" human code
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        let encoded = encode_chunk(value.as_ref(), ECC, BITS).map_err(S::Error::custom)?;
+        serializer.serialize_str(encoded.pretty().as_str())
+    }
+
+    /// Deserialize a human code, correcting any errors in it
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: for<'a> TryFrom<&'a [u8]>,
+        D: Deserializer<'de>,
+    {
+        struct CodeVisitor<T, const ECC: u8, const BITS: u8> {
+            _marker: PhantomData<T>,
+        }
+
+        impl<'de, T, const ECC: u8, const BITS: u8> Visitor<'de> for CodeVisitor<T, ECC, BITS>
+        where
+            T: for<'a> TryFrom<&'a [u8]>,
+        {
+            type Value = T;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a human code with {} bits and {} ecc symbols", BITS, ECC)
+            }
+
+            fn visit_str<E: DeError>(self, v: &str) -> Result<T, E> {
+                let (decoded, _) = decode_chunk(v, ECC, BITS).map_err(E::custom)?;
+                T::try_from(decoded.as_bytes()).map_err(|_| {
+                    E::custom("the decoded code couldn't be converted to the field type")
+                })
+            }
+        }
+
+        deserializer.deserialize_str(CodeVisitor::<T, ECC, BITS> {
+            _marker: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,239 @@
+use crate::bits::BitReader;
+use crate::decode_chunk::decode_chunk;
+use crate::encode_chunk::EncodedChunk;
+use crate::error::{
+    inconsistent_shares, invalid_ecc_len, invalid_secret_length, not_enough_shares, HumancodeError,
+};
+#[cfg(feature = "rand")]
+use crate::{bits::BitWriter, encode_chunk::encode_chunk, error::invalid_share_parameters};
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+// Every code starts with the index of the share (8 bits), the threshold
+// (8 bits), the part number within the share (4 bits), and the length of the
+// secret minus 1 (8 bits)
+const HEADER_BITS: u8 = 28;
+const MAX_PARTS: usize = 16;
+
+/// One share of a secret that was split with [`split_secret`].
+///
+/// A share is made up of one or more codes, which all have to be entered
+/// to use the share.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShamirShare {
+    index: u8,
+    codes: Vec<EncodedChunk>,
+}
+
+impl ShamirShare {
+    /// Get the index of the share, which is in the range [1,255]
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Get the codes that make up the share
+    pub fn codes(&self) -> &[EncodedChunk] {
+        &self.codes
+    }
+}
+
+/// Get the number of bits in each code of a share for the given number of
+/// error correcting symbols - this is the `bits` value needed to decode the
+/// codes individually.
+///
+/// Each code is as large as possible given `ecc`, so fewer error correcting
+/// symbols means fewer codes per share.
+pub fn share_code_bits(ecc: u8) -> Result<u8, HumancodeError> {
+    let bytes = bytes_per_code(ecc)?;
+    Ok(HEADER_BITS + 8 * bytes as u8)
+}
+
+/// Split `secret` into `shares` shares, any `threshold` of which can be
+/// combined to get the secret back with [`combine_shares`].
+///
+/// The secret is split byte by byte with Shamir's secret sharing over
+/// GF(2^8). Each share is encoded into codes with `ecc` error correcting
+/// symbols. Each code holds the index of its share, the threshold, the
+/// length of the secret, and up to [`share_code_bits`] bits of the share - a
+/// share can have at most 16 codes. Fewer than `threshold` shares reveal
+/// nothing about the secret.
+///
+/// `threshold` must be at least 1 and no larger than `shares`.
+///
+/// The temporary buffers that hold the random coefficients and the shares
+/// are not zeroed before they are freed, so copies of secret material may be
+/// left behind in memory.
+#[cfg(feature = "rand")]
+pub fn split_secret<R: RngCore + CryptoRng + ?Sized>(
+    secret: &[u8],
+    threshold: u8,
+    shares: u8,
+    ecc: u8,
+    rng: &mut R,
+) -> Result<Vec<ShamirShare>, HumancodeError> {
+    if threshold == 0 || threshold > shares {
+        return Err(invalid_share_parameters());
+    }
+    let bytes = bytes_per_code(ecc)?;
+    let parts = parts_for_secret(secret.len(), bytes)?;
+
+    // Each byte of the secret is the constant term of its own random
+    // polynomial of degree `threshold - 1`.
+    let mut coefficients = vec![0u8; secret.len() * (threshold as usize - 1)];
+    rng.fill_bytes(&mut coefficients);
+
+    let mut result = Vec::with_capacity(shares as usize);
+    for index in 1..=shares {
+        let mut share = vec![0u8; parts * bytes];
+        for (i, (&s, y)) in secret.iter().zip(share.iter_mut()).enumerate() {
+            let poly =
+                &coefficients[i * (threshold as usize - 1)..(i + 1) * (threshold as usize - 1)];
+            // Horner's method, from the highest coefficient down
+            let mut value = 0u8;
+            for &c in poly.iter().rev() {
+                value = gf_mul(value, index) ^ c;
+            }
+            *y = gf_mul(value, index) ^ s;
+        }
+
+        let mut codes = Vec::with_capacity(parts);
+        for (part, data) in share.chunks(bytes).enumerate() {
+            let mut writer = BitWriter::new();
+            writer.write(index as u64, 8)?;
+            writer.write(threshold as u64, 8)?;
+            writer.write(part as u64, 4)?;
+            writer.write(secret.len() as u64 - 1, 8)?;
+            writer.write_bytes(data)?;
+            codes.push(encode_chunk(writer.as_bytes(), ecc, writer.bits())?);
+        }
+        result.push(ShamirShare { index, codes });
+    }
+    Ok(result)
+}
+
+/// Reconstruct a secret from codes that were created by [`split_secret`].
+///
+/// `codes` may contain the codes of any number of shares, in any order, and
+/// each code is corrected before it is used. `ecc` must match the value that
+/// was passed to `split_secret`. At least `threshold` complete shares are
+/// required - an
+/// [`InputError`](crate::HumancodeError::InputError) is returned if there
+/// aren't enough shares or if the codes don't belong to the same secret.
+///
+/// Neither the returned secret nor the temporary buffers that hold the
+/// shares are zeroed before they are freed, so callers that need that have
+/// to zero the returned secret themselves, and copies of the shares may still
+/// be left behind in memory.
+pub fn combine_shares<S: AsRef<str>>(codes: &[S], ecc: u8) -> Result<Vec<u8>, HumancodeError> {
+    let bytes = bytes_per_code(ecc)?;
+    let bits = HEADER_BITS + 8 * bytes as u8;
+
+    // The threshold and the length of the secret are in every code, so they
+    // have to agree
+    let mut params = None;
+    let mut shares: BTreeMap<u8, Vec<Option<Vec<u8>>>> = BTreeMap::new();
+    for code in codes.iter() {
+        let (decoded, _) = decode_chunk(code.as_ref(), ecc, bits)?;
+        let mut reader = BitReader::new(&decoded, bits)?;
+        let index = reader.read(8)? as u8;
+        let threshold = reader.read(8)? as u8;
+        let part = reader.read(4)? as usize;
+        let secret_len = reader.read(8)? as usize + 1;
+        let mut data = vec![0u8; bytes];
+        reader.read_bytes(&mut data)?;
+
+        let parts = parts_for_secret(secret_len, bytes).map_err(|_| inconsistent_shares())?;
+        if index == 0 || threshold == 0 || part >= parts {
+            return Err(inconsistent_shares());
+        }
+        if *params.get_or_insert((threshold, secret_len)) != (threshold, secret_len) {
+            return Err(inconsistent_shares());
+        }
+        let share = shares.entry(index).or_insert_with(|| vec![None; parts]);
+        match &share[part] {
+            Some(existing) if *existing != data => return Err(inconsistent_shares()),
+            _ => share[part] = Some(data),
+        }
+    }
+
+    let (threshold, secret_len) = params.ok_or_else(not_enough_shares)?;
+    let threshold = threshold as usize;
+    let mut complete: Vec<(u8, Vec<u8>)> = Vec::with_capacity(threshold);
+    for (index, share) in shares.into_iter() {
+        if complete.len() == threshold || !share.iter().all(|part| part.is_some()) {
+            continue;
+        }
+        let mut values = Vec::with_capacity(share.len() * bytes);
+        for part in share.iter().flatten() {
+            values.extend_from_slice(part);
+        }
+        complete.push((index, values));
+    }
+    if complete.len() < threshold {
+        return Err(not_enough_shares());
+    }
+
+    // Lagrange interpolation at x = 0
+    let mut secret = vec![0u8; secret_len];
+    for (i, (xi, yi)) in complete.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, (xj, _)) in complete.iter().enumerate() {
+            if i != j {
+                basis = gf_mul(basis, gf_mul(*xj, gf_inverse(xj ^ xi)));
+            }
+        }
+        for (s, &y) in secret.iter_mut().zip(yi.iter()) {
+            *s ^= gf_mul(y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+// The number of bytes of a share that fit in each code
+fn bytes_per_code(ecc: u8) -> Result<usize, HumancodeError> {
+    if ecc > 30 {
+        return Err(invalid_ecc_len());
+    }
+    let bits = (5 * (31 - ecc as usize)).min(150);
+    match bits.saturating_sub(HEADER_BITS as usize) / 8 {
+        0 => Err(invalid_secret_length()),
+        bytes => Ok(bytes),
+    }
+}
+
+fn parts_for_secret(secret_len: usize, bytes: usize) -> Result<usize, HumancodeError> {
+    let parts = secret_len.div_ceil(bytes);
+    if parts == 0 || parts > MAX_PARTS {
+        return Err(invalid_secret_length());
+    }
+    Ok(parts)
+}
+
+// Multiplication in GF(2^8) with the AES polynomial
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            result ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    result
+}
+
+// a^254 is the inverse of a, since a^255 = 1 for every non-zero a
+fn gf_inverse(a: u8) -> u8 {
+    let mut result = 1;
+    for _ in 0..254 {
+        result = gf_mul(result, a);
+    }
+    result
+}
@@ -0,0 +1,277 @@
+use crate::bits::{BitReader, BitWriter};
+use crate::decode_chunk::decode_chunk;
+use crate::encode_chunk::{encode_chunk, EncodedChunk};
+use crate::error::{
+    decode_buffer_wrong_size, invalid_ecc_len, invalid_sheet_layout, HumancodeError,
+};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use libzbase32::low_level_encode::required_quintets_buffer_len;
+
+// Every line starts with the index of the line (7 bits) and a flag that
+// is set on the last line (1 bit)
+const HEADER_BITS: u8 = 8;
+const MAX_LINES: usize = 128;
+
+/// A `BackupSheet` lays out arbitrary bytes - for example, a long key - as
+/// numbered lines of codes that can be printed and typed back in later.
+///
+/// Each line is its own code with its own error correcting symbols, so
+/// typos in one line don't affect the others. Every line also holds its
+/// position and whether it's the last line, so missing or reordered lines
+/// are detected when the sheet is parsed with [`parse_backup_sheet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackupSheet {
+    lines: Vec<EncodedChunk>,
+}
+
+impl BackupSheet {
+    /// Lay out `data` with `bytes_per_line` bytes in each line and `ecc` error
+    /// correcting symbols per line. The last line may hold fewer bytes.
+    ///
+    /// `bytes_per_line` must be at least 1 and the data must fit in 128
+    /// lines. Each line must fit in a single code, so `bytes_per_line` is
+    /// at most 17, and less with more error correcting symbols.
+    pub fn new(data: &[u8], bytes_per_line: u8, ecc: u8) -> Result<BackupSheet, HumancodeError> {
+        if bytes_per_line == 0 {
+            return Err(invalid_sheet_layout());
+        }
+        let chunks: Vec<&[u8]> = if data.is_empty() {
+            alloc::vec![&[][..]]
+        } else {
+            data.chunks(bytes_per_line as usize).collect()
+        };
+        if chunks.len() > MAX_LINES {
+            return Err(invalid_sheet_layout());
+        }
+
+        let mut lines = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            let mut writer = BitWriter::new();
+            writer.write(index as u64, 7)?;
+            writer.write_bool(index + 1 == chunks.len())?;
+            writer.write_bytes(chunk)?;
+            lines.push(encode_chunk(writer.as_bytes(), ecc, writer.bits())?);
+        }
+        Ok(BackupSheet { lines })
+    }
+
+    /// Get the codes for each line
+    pub fn lines(&self) -> &[EncodedChunk] {
+        &self.lines
+    }
+
+    /// Format the sheet as plain text, one numbered line per code
+    pub fn to_text(&self) -> String {
+        let width = digits(self.lines.len()).max(2);
+        let mut out = String::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            writeln!(out, "{:0width$}  {}", i + 1, line.pretty(), width = width)
+                .expect("Writing to a String can't fail");
+        }
+        out
+    }
+
+    /// Format the sheet as a Markdown document with a title, a short
+    /// explanation, and the numbered lines in a code block.
+    ///
+    /// The whole document can be passed to [`parse_backup_sheet`].
+    pub fn to_markdown(&self, title: &str) -> String {
+        format!(
+            "# {}\n\nKeep this sheet somewhere safe. Type every line back in, including the line numbers, to recover the data. Use \"!\" for any character that can't be read.\n\n```text\n{}```\n",
+            title,
+            self.to_text()
+        )
+    }
+}
+
+/// The result of parsing one line of a backup sheet
+#[derive(Debug)]
+pub enum LineStatus {
+    /// The line was read without any errors
+    Ok,
+    /// The line had errors which were corrected. The corrected code is
+    /// included so that it can be confirmed with the user.
+    Corrected(EncodedChunk),
+    /// The line couldn't be read
+    Invalid(HumancodeError),
+    /// The line belongs to a different position than other lines with the
+    /// same line number, or a different sheet
+    Conflicting,
+    /// The line wasn't found in the sheet
+    Missing,
+}
+
+/// A report on a single line of a backup sheet
+#[derive(Debug)]
+pub struct LineReport {
+    line_number: usize,
+    status: LineStatus,
+}
+
+impl LineReport {
+    /// Get the number of the line, starting at 1
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Get the result of parsing the line
+    pub fn status(&self) -> &LineStatus {
+        &self.status
+    }
+
+    /// Check if the line couldn't be used
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.status,
+            LineStatus::Invalid(_) | LineStatus::Conflicting | LineStatus::Missing
+        )
+    }
+}
+
+/// The result of parsing a backup sheet with [`parse_backup_sheet`]
+#[derive(Debug)]
+pub struct SheetReport {
+    data: Option<Vec<u8>>,
+    lines: Vec<LineReport>,
+}
+
+impl SheetReport {
+    /// Get the recovered data - this is only available if every line of
+    /// the sheet was read successfully.
+    pub fn data(&self) -> Option<&[u8]> {
+        self.data.as_deref()
+    }
+
+    /// Get a report for every line that was found in the sheet, in the
+    /// order they were found, followed by any missing lines
+    pub fn lines(&self) -> &[LineReport] {
+        &self.lines
+    }
+
+    /// Iterate over the lines that had errors that couldn't be corrected
+    pub fn errors(&self) -> impl Iterator<Item = &LineReport> + '_ {
+        self.lines.iter().filter(|line| line.is_error())
+    }
+}
+
+/// Parse a backup sheet that was created by [`BackupSheet`] and typed
+/// back in.
+///
+/// `sheet` may be the output of [`to_text`](BackupSheet::to_text) or
+/// [`to_markdown`](BackupSheet::to_markdown). Lines that don't start with
+/// a line number are ignored, as are blank lines. The line number may be
+/// followed by ":", ".", or ")" and spaces and "-" within the code are
+/// ignored. `ecc` must match the value that was used to create the sheet.
+///
+/// Every line is corrected separately and the result for each line is
+/// included in the returned [`SheetReport`]. An error is only returned
+/// for invalid parameters.
+pub fn parse_backup_sheet(sheet: &str, ecc: u8) -> Result<SheetReport, HumancodeError> {
+    if ecc > 30 {
+        return Err(invalid_ecc_len());
+    }
+
+    let mut reports = Vec::new();
+    let mut found: BTreeMap<usize, (Vec<u8>, bool)> = BTreeMap::new();
+    for text in sheet.lines() {
+        let text = text.trim();
+        let digits_len = text.bytes().take_while(|x| x.is_ascii_digit()).count();
+        if digits_len == 0 {
+            continue;
+        }
+        let line_number: usize = text[..digits_len].parse().unwrap_or(usize::MAX);
+        let rest = text[digits_len..].trim_start_matches([':', '.', ')']);
+        let code: String = rest.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let status = match parse_line(&code, ecc) {
+            Ok((index, last, data, corrected)) => match found.get(&index) {
+                Some(existing) if *existing != (data.clone(), last) => LineStatus::Conflicting,
+                _ if line_number != index + 1 => LineStatus::Conflicting,
+                _ => {
+                    found.insert(index, (data, last));
+                    match corrected {
+                        Some(code) => LineStatus::Corrected(code),
+                        None => LineStatus::Ok,
+                    }
+                }
+            },
+            Err(err) => LineStatus::Invalid(err),
+        };
+        reports.push(LineReport {
+            line_number,
+            status,
+        });
+    }
+
+    // Every line up to the one marked as the last must be present - if no
+    // line was marked as the last line, there is at least one more.
+    let last_index = found
+        .iter()
+        .find(|(_, (_, last))| *last)
+        .map(|(&index, _)| index);
+    let expected_lines = match last_index {
+        Some(index) => index + 1,
+        None => found.keys().next_back().map_or(0, |&index| index + 1) + 1,
+    };
+    for index in 0..expected_lines {
+        if !found.contains_key(&index) && !reports.iter().any(|r| r.line_number == index + 1) {
+            reports.push(LineReport {
+                line_number: index + 1,
+                status: LineStatus::Missing,
+            });
+        }
+    }
+
+    let complete = reports.iter().all(|r| !r.is_error())
+        && (0..expected_lines).all(|index| found.contains_key(&index))
+        && found.len() == expected_lines;
+    let data = if complete {
+        Some(found.into_values().flat_map(|(data, _)| data).collect())
+    } else {
+        None
+    };
+
+    Ok(SheetReport {
+        data,
+        lines: reports,
+    })
+}
+
+type ParsedLine = (usize, bool, Vec<u8>, Option<EncodedChunk>);
+
+fn parse_line(code: &str, ecc: u8) -> Result<ParsedLine, HumancodeError> {
+    let len = code.bytes().filter(|&x| x != b'-').count();
+    let bits = bits_for_line_len(len, ecc).ok_or_else(decode_buffer_wrong_size)?;
+    let (decoded, corrected) = decode_chunk(code, ecc, bits)?;
+    let mut reader = BitReader::new(&decoded, bits)?;
+    let index = reader.read(7)? as usize;
+    let last = reader.read_bool()?;
+    let mut data = alloc::vec![0u8; reader.remaining() as usize / 8];
+    reader.read_bytes(&mut data)?;
+    Ok((index, last, data, corrected))
+}
+
+// Figure out how many bytes a line holds from the number of characters in
+// it. Each extra byte adds at least one character, so the length of a line
+// is enough to tell how many bytes it holds.
+fn bits_for_line_len(len: usize, ecc: u8) -> Option<u8> {
+    (0..=17u8)
+        .map(|bytes| HEADER_BITS + 8 * bytes)
+        .find(|&bits| {
+            required_quintets_buffer_len(bits as u64)
+                .is_ok_and(|quintets| quintets + ecc as usize == len)
+        })
+}
+
+fn digits(mut value: usize) -> usize {
+    let mut count = 1;
+    while value >= 10 {
+        value /= 10;
+        count += 1;
+    }
+    count
+}
@@ -20,4 +20,8 @@ impl<const N: usize> SmallByteBuf<N> {
     pub fn as_mut_bytes(&mut self) -> &mut [u8] {
         &mut self.buf[0..self.len as usize]
     }
+
+    pub fn into_inner(self) -> ([u8; N], usize) {
+        (self.buf, self.len as usize)
+    }
 }
@@ -1,4 +1,63 @@
-use crate::{decode_chunk, encode_chunk};
+use crate::{
+    decode_chunk, encode_chunk, parse_chunk, BitReader, BitWriter, Blocklist, ChunkFormat,
+    EncodedChunk, EncodedChunkPretty, EncodedChunkRaw, Expiry, KeyedPermutation, DEFAULT_BLOCKLIST,
+};
+use crate::{HumancodeError, InputErrorKind};
+use core::fmt::Write;
+
+// A fixed size buffer to format into, so that messages can be checked
+// without an allocator
+struct FmtBuf {
+    buf: [u8; 256],
+    len: usize,
+}
+
+impl FmtBuf {
+    fn new() -> FmtBuf {
+        FmtBuf {
+            buf: [0u8; 256],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl Write for FmtBuf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(core::fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+// Check that an operation failed with an input error of the expected kind
+fn assert_input_error<T>(result: Result<T, HumancodeError>, kind: InputErrorKind) {
+    match result {
+        Err(HumancodeError::InputError(cause)) => assert_eq!(cause.kind(), kind),
+        Err(err) => panic!("Expected an input error, but got: {}", err),
+        Ok(_) => panic!("The operation didn't fail"),
+    }
+}
+
+// Check that an operation failed with the expected error
+fn assert_error<T>(result: Result<T, HumancodeError>, expected: HumancodeError) {
+    let err = match result {
+        Ok(_) => panic!("The operation didn't fail"),
+        Err(err) => err,
+    };
+    let mut actual_msg = FmtBuf::new();
+    write!(actual_msg, "{}", err).unwrap();
+    let mut expected_msg = FmtBuf::new();
+    write!(expected_msg, "{}", expected).unwrap();
+    assert_eq!(actual_msg.as_str(), expected_msg.as_str());
+}
 
 #[test]
 fn test_happy_path() {
@@ -46,3 +105,1614 @@ fn test_invalid_trailing_octet() {
     let (decoded, _) = decode_chunk(BAD_CODE, 5, 128).unwrap();
     assert_eq!(decoded.as_bytes(), VALUE);
 }
+
+#[test]
+fn test_bit_writer_reader_round_trip() {
+    let mut writer = BitWriter::new();
+    writer.write(5, 3).unwrap();
+    writer.write_bool(true).unwrap();
+    writer.write(0x1234_5678_9abc, 48).unwrap();
+    writer.write_bytes(&[0xde, 0xad]).unwrap();
+    writer.write(1, 2).unwrap();
+    assert_eq!(writer.bits(), 70);
+    assert_eq!(writer.as_bytes().len(), 9);
+
+    let encoded = encode_chunk(writer.as_bytes(), 4, writer.bits()).unwrap();
+    let (decoded, _) = decode_chunk(encoded.raw().as_str(), 4, 70).unwrap();
+
+    let mut reader = BitReader::new(decoded.as_bytes(), 70).unwrap();
+    assert_eq!(reader.read(3).unwrap(), 5);
+    assert!(reader.read_bool().unwrap());
+    assert_eq!(reader.read(48).unwrap(), 0x1234_5678_9abc);
+    let mut bytes = [0u8; 2];
+    reader.read_bytes(&mut bytes).unwrap();
+    assert_eq!(bytes, [0xde, 0xad]);
+    assert_eq!(reader.read(2).unwrap(), 1);
+    assert_eq!(reader.remaining(), 0);
+    assert!(reader.read(1).is_err());
+}
+
+#[test]
+fn test_bit_writer_limits() {
+    let mut writer = BitWriter::new();
+    assert!(writer.write(4, 2).is_err());
+    assert!(writer.write(0, 65).is_err());
+    writer.write(u64::MAX, 64).unwrap();
+    writer.write(u64::MAX, 64).unwrap();
+    assert!(writer.write(0, 23).is_err());
+    writer.write(0, 22).unwrap();
+    assert_eq!(writer.bits(), 150);
+    assert!(writer.write_bool(false).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_pretty_code() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "crate::SerdePrettyCode::<5, 128>")]
+        key: [u8; 16],
+    }
+
+    let config = Config {
+        key: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"key":"yyyo-ryar-ywdy-qnyj-befo-adeq-bhix-4os"}"#);
+
+    let corrupted = r#"{"key":"!!yo-ryar-ywdy-qnyj-befo-adeq-bhix-4os"}"#;
+    assert_eq!(serde_json::from_str::<Config>(corrupted).unwrap(), config);
+
+    let too_corrupted = r#"{"key":"!!!!-!!ar-ywdy-qnyj-befo-adeq-bhix-4os"}"#;
+    assert!(serde_json::from_str::<Config>(too_corrupted).is_err());
+
+    let encoded = encode_chunk(&config.key, 5, 128).unwrap();
+    assert_eq!(
+        serde_json::to_string(&encoded.raw()).unwrap(),
+        r#""yyyoryarywdyqnyjbefoadeqbhix4os""#
+    );
+}
+
+#[test]
+fn test_encoded_chunk_comparisons() {
+    const CODE: &str = "yyyo-ryar-ywdy-qnyj-befo-adeq-bhix-4os";
+    const DATA: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    let encoded = encode_chunk(DATA, 5, 128).unwrap();
+    assert_eq!(encoded.raw(), encoded.pretty());
+    assert_eq!(encoded.pretty(), encoded.raw());
+    assert!(encoded.raw() == CODE);
+    assert!(encoded.pretty() == "yyyoryarywdyqnyjbefoadeqbhix4os");
+    assert!(encoded.raw() != "yyyo");
+
+    let parsed: EncodedChunk = CODE.parse().unwrap();
+    assert_eq!(parsed, encoded);
+    assert_eq!(CODE.parse::<EncodedChunkPretty>().unwrap().as_str(), CODE);
+    assert!("yyyo-!yar".parse::<EncodedChunk>().is_err());
+    assert!("--".parse::<EncodedChunk>().is_err());
+
+    #[cfg(feature = "std")]
+    {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(encoded.raw());
+        assert!(set.contains(&encoded.pretty().as_str().parse().unwrap()));
+    }
+
+    // "y" is the z-base-32 character for 0 and "9" is the character for 31
+    let low: EncodedChunkRaw = "yy".parse().unwrap();
+    let high: EncodedChunkRaw = "y9".parse().unwrap();
+    assert!(low < high);
+}
+
+#[test]
+fn test_parse_chunk() {
+    const CORRECT_CODE: &str = "yyyo-ryar-ywdy-qnyj-befo-adeq-bhix-4os";
+    const INVALID_CODE: &str = "!!yo-ryar-ywdy-qnyj-befo-adeq-bhix-4os";
+
+    let parsed = parse_chunk(CORRECT_CODE, 5, 128).unwrap();
+    assert!(parsed.pretty() == CORRECT_CODE);
+
+    let corrected = parse_chunk(INVALID_CODE, 5, 128).unwrap();
+    assert_eq!(corrected, parsed);
+    assert!(corrected.pretty() != INVALID_CODE);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_decoded_chunk_conversions() {
+    use alloc::vec::Vec;
+    use core::convert::TryFrom;
+
+    const DATA: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    let encoded = encode_chunk(&DATA, 5, 128).unwrap();
+    let (decoded, _) = decode_chunk(encoded.raw().as_str(), 5, 128).unwrap();
+
+    assert_eq!(<[u8; 16]>::try_from(decoded).unwrap(), DATA);
+    assert!(<[u8; 15]>::try_from(decoded).is_err());
+    assert!(<[u8; 17]>::try_from(decoded).is_err());
+
+    assert_eq!(decoded.len(), 16);
+    assert_eq!(&decoded[..4], &DATA[..4]);
+    assert_eq!(decoded.to_vec(), DATA.to_vec());
+    assert_eq!(Vec::from(decoded), DATA.to_vec());
+
+    let (buf, len) = decoded.into_inner();
+    assert_eq!(&buf[..len], &DATA);
+    assert!(buf[len..].iter().all(|&x| x == 0));
+}
+
+#[test]
+fn test_encode_into_buffers_and_writers() {
+    const DATA: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    let encoded = encode_chunk(DATA, 5, 128).unwrap();
+
+    let mut buf = [0u8; 38];
+    let len = encoded.encode_into(ChunkFormat::Pretty, &mut buf).unwrap();
+    assert_eq!(&buf[..len], encoded.pretty().as_str().as_bytes());
+    let len = encoded.encode_into(ChunkFormat::Raw, &mut buf).unwrap();
+    assert_eq!(&buf[..len], encoded.raw().as_str().as_bytes());
+    assert!(encoded
+        .encode_into(ChunkFormat::Pretty, &mut buf[..37])
+        .is_err());
+
+    let mut s = FmtBuf::new();
+    encoded.write_to(ChunkFormat::Pretty, &mut s).unwrap();
+    assert_eq!(s.as_str(), encoded.pretty().as_str());
+    assert_eq!(encoded.encoded_len(ChunkFormat::Pretty), s.as_str().len());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_encode_into_io_writer() {
+    const DATA: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    let encoded = encode_chunk(DATA, 5, 128).unwrap();
+    let mut v = Vec::new();
+    encoded.write_to_io(ChunkFormat::Raw, &mut v).unwrap();
+    assert_eq!(v, encoded.raw().as_str().as_bytes());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_confirm_correction() {
+    use crate::{decode_chunk_interactive, HighlightStyle};
+
+    const DATA: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    const INVALID_CODE: &str = "!!yo-ryar-ywdy-qnyjbefo-adeq-bhix-4os";
+
+    let mut output = Vec::new();
+    let decoded = decode_chunk_interactive(
+        INVALID_CODE,
+        5,
+        128,
+        HighlightStyle::Markers,
+        &mut &b"y\n"[..],
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(decoded.unwrap().as_bytes(), DATA);
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0],
+        "You entered:    !!yo-ryar-ywdy-qnyj-befo-adeq-bhix-4os"
+    );
+    assert_eq!(
+        lines[1],
+        "Corrected code: yyyo-ryar-ywdy-qnyj-befo-adeq-bhix-4os"
+    );
+    assert_eq!(lines[2], "                ^^");
+
+    let mut output = Vec::new();
+    let decoded = decode_chunk_interactive(
+        INVALID_CODE,
+        5,
+        128,
+        HighlightStyle::Ansi,
+        &mut &b"\n"[..],
+        &mut output,
+    )
+    .unwrap();
+    assert!(decoded.is_none());
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("\x1b[1;32my\x1b[0m"));
+
+    // No prompt is shown if there was nothing to correct
+    let mut output = Vec::new();
+    let encoded = encode_chunk(DATA, 5, 128).unwrap();
+    let decoded = decode_chunk_interactive(
+        encoded.raw().as_str(),
+        5,
+        128,
+        HighlightStyle::Markers,
+        &mut &b""[..],
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(decoded.unwrap().as_bytes(), DATA);
+    assert!(output.is_empty());
+}
+
+// A deterministic RNG for tests. It is _not_ actually cryptographically secure.
+struct TestRng(u64);
+
+impl TestRng {
+    fn next(&mut self) -> u64 {
+        // xorshift64*
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Get a value in the range [0, n)
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand_core::RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.next()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand_core::CryptoRng for TestRng {}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_generate() {
+    use crate::encoder::CHUNK_ENCODER_3;
+    use crate::generate;
+
+    let mut rng = TestRng(1);
+    // 135 bits is the most that can be encoded with 4 ecc symbols
+    for bits in 1..=135 {
+        let (data, encoded) = generate(4, bits, &mut rng).unwrap();
+        let (decoded, corrected) = decode_chunk(encoded.raw().as_str(), 4, bits).unwrap();
+        assert_eq!(decoded.as_bytes(), data.as_bytes());
+        assert!(corrected.is_none());
+
+        let (data, encoded) = CHUNK_ENCODER_3.generate(bits, &mut rng).unwrap();
+        assert_eq!(encode_chunk(&data, 3, bits).unwrap(), encoded);
+    }
+    assert!(generate(4, 0, &mut rng).is_err());
+    assert!(generate(4, 151, &mut rng).is_err());
+    assert!(generate(31, 8, &mut rng).is_err());
+}
+
+#[test]
+fn test_blocklist() {
+    // "yyyo-ryar-ywdy-qnyj-befo-adeq-bhix-4os"
+    const DATA: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    let encoded = encode_chunk(DATA, 5, 128).unwrap();
+    assert_eq!(Blocklist::new(&["QNYJ"]).find(&encoded), Some("QNYJ"));
+    // Words that cross a group boundary are still found
+    assert_eq!(
+        Blocklist::new(&["abc", "yjbe"]).find(&encoded),
+        Some("yjbe")
+    );
+    assert!(Blocklist::new(&["o-ry"]).is_blocked(&encoded));
+    assert!(!Blocklist::new(&["abc"]).is_blocked(&encoded));
+    assert!(!DEFAULT_BLOCKLIST.is_blocked(&encoded));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_blocklist_generate() {
+    let mut rng = TestRng(2);
+    let blocklist = Blocklist::new(&["y", "b", "n", "d", "r", "f", "g", "8"]);
+    for _ in 0..100 {
+        let (data, encoded) = blocklist.generate(5, 40, 1000, &mut rng).unwrap();
+        assert!(!blocklist.is_blocked(&encoded));
+        assert_eq!(encode_chunk(&data, 5, 40).unwrap(), encoded);
+    }
+
+    let everything = Blocklist::new(&[
+        "y", "b", "n", "d", "r", "f", "g", "8", "e", "j", "k", "m", "c", "p", "q", "x", "o", "t",
+        "1", "u", "w", "i", "s", "z", "a", "3", "4", "5", "h", "7", "6", "9",
+    ]);
+    assert!(everything.generate(5, 40, 10, &mut rng).is_err());
+}
+
+#[cfg(all(feature = "rand", feature = "alloc"))]
+#[test]
+fn test_generate_batch() {
+    use crate::generate_batch;
+    use alloc::vec::Vec;
+
+    let mut rng = TestRng(3);
+
+    // 16 bits and 2 ecc symbols gives codes that are 6 symbols long
+    let batch = generate_batch(2, 16, 300, 4, &mut rng).unwrap();
+    assert_eq!(batch.len(), 300);
+    for (i, (data, a)) in batch.iter().enumerate() {
+        assert_eq!(encode_chunk(data, 2, 16).unwrap(), *a);
+        for (_, b) in batch[i + 1..].iter() {
+            assert!(a.distance(b).unwrap() >= 4);
+        }
+    }
+
+    // Only uniqueness is required
+    let batch = generate_batch(0, 8, 256, 1, &mut rng).unwrap();
+    let mut all: Vec<u8> = batch.iter().map(|(data, _)| data[0]).collect();
+    all.sort_unstable();
+    all.dedup();
+    assert_eq!(all.len(), 256);
+
+    // Too many codes for the number of bits
+    assert!(generate_batch(2, 8, 257, 1, &mut rng).is_err());
+    // The distance is longer than the code
+    assert!(generate_batch(2, 16, 10, 7, &mut rng).is_err());
+    // Singleton bound - at most 32^2 codes can be 5 symbols apart
+    assert!(generate_batch(2, 16, 1025, 5, &mut rng).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_code_book() {
+    use crate::CodeBook;
+
+    const ECC: u8 = 2;
+    const BITS: u8 = 16;
+
+    let mut book = CodeBook::new(ECC, BITS).unwrap();
+    assert!(book.is_empty());
+    let a = encode_chunk(&[0x12, 0x34], ECC, BITS).unwrap();
+    let b = encode_chunk(&[0xAB, 0xCD], ECC, BITS).unwrap();
+    assert!(book.insert(a).unwrap());
+    assert!(book.insert(b).unwrap());
+    assert!(!book.insert(a).unwrap());
+    assert_eq!(book.len(), 2);
+    assert!(book.contains(&a));
+
+    // Codes with the wrong length or invalid ECC symbols are rejected
+    assert!(book
+        .insert(encode_chunk(&[0x12, 0x34], 3, BITS).unwrap())
+        .is_err());
+    let mut bad = [0u8; 6];
+    a.encode_into(ChunkFormat::Raw, &mut bad).unwrap();
+    bad[5] = if bad[5] == b'y' { b'b' } else { b'y' };
+    assert!(book
+        .insert(core::str::from_utf8(&bad).unwrap().parse().unwrap())
+        .is_err());
+
+    // Exact match
+    let m = book.find_nearest(a.pretty().as_str(), 2).unwrap();
+    assert_eq!(m.len(), 1);
+    assert_eq!(m[0].code(), a);
+    assert_eq!(m[0].distance(), 0);
+
+    // A single error is corrected with the ECC symbols
+    let m = book
+        .find_nearest(core::str::from_utf8(&bad).unwrap(), 2)
+        .unwrap();
+    assert_eq!(m.len(), 1);
+    assert_eq!(m[0].code(), a);
+    assert_eq!(m[0].distance(), 1);
+
+    // With 3 errors, ECC can't help but the CodeBook still finds the code
+    let mut typo = [0u8; 6];
+    b.encode_into(ChunkFormat::Raw, &mut typo).unwrap();
+    typo[0] = b'!';
+    typo[1] = b'!';
+    typo[2] = b'!';
+    let typo = core::str::from_utf8(&typo).unwrap();
+    assert!(decode_chunk(typo, ECC, BITS).is_err());
+    let m = book.find_nearest(typo, 3).unwrap();
+    assert_eq!(m.len(), 1);
+    assert_eq!(m[0].code(), b);
+    assert_eq!(m[0].distance(), 3);
+    assert!(book.find_nearest(typo, 2).unwrap().is_empty());
+
+    // Every code is equally far away from garbage
+    let m = book.find_nearest("!!!!!!", 6).unwrap();
+    assert_eq!(m.len(), 2);
+
+    assert!(book.find_nearest("!!!!!", 6).is_err());
+    assert!(book.remove(&a));
+    assert!(!book.contains(&a));
+    assert!(CodeBook::new(31, BITS).is_err());
+    assert!(CodeBook::new(ECC, 0).is_err());
+}
+
+#[test]
+fn test_keyed_permutation() {
+    const KEY: [u8; 16] = [7; 16];
+    let perm = KeyedPermutation::new(&KEY);
+
+    // Every value of a small width maps to a different value
+    for bits in 1..=10u8 {
+        let mut seen = [false; 1024];
+        for value in 0u16..(1 << bits) {
+            let original = (value << (16 - bits)).to_be_bytes();
+            let len = (bits as usize).div_ceil(8);
+            let mut data = original;
+            perm.permute(&mut data[..len], bits).unwrap();
+            let permuted = u16::from_be_bytes(data) >> (16 - bits);
+            assert!(!seen[permuted as usize]);
+            seen[permuted as usize] = true;
+            perm.unpermute(&mut data[..len], bits).unwrap();
+            assert_eq!(data, original);
+        }
+    }
+
+    // Round trip for every width, through encoding and decoding
+    for bits in 1..=150u8 {
+        let len = (bits as usize).div_ceil(8);
+        let mut data = [0u8; 19];
+        for (i, x) in data[..len].iter_mut().enumerate() {
+            *x = (i as u8).wrapping_mul(37);
+        }
+        if bits % 8 != 0 {
+            data[len - 1] &= 0xFF << (8 - bits % 8);
+        }
+        let encoded = perm.encode_chunk(&data[..len], 0, bits).unwrap();
+        let (decoded, corrected) = perm.decode_chunk(encoded.raw().as_str(), 0, bits).unwrap();
+        assert!(corrected.is_none());
+        assert_eq!(decoded.as_bytes(), &data[..len]);
+    }
+
+    // Consecutive IDs don't produce consecutive values and different keys
+    // give different permutations
+    let mut a = [0, 1];
+    let mut b = [0, 2];
+    perm.permute(&mut a, 16).unwrap();
+    perm.permute(&mut b, 16).unwrap();
+    assert_ne!(u16::from_be_bytes(a).wrapping_add(1), u16::from_be_bytes(b));
+    let mut c = [0, 1];
+    KeyedPermutation::new(&[8; 16]).permute(&mut c, 16).unwrap();
+    assert_ne!(a, c);
+
+    assert!(perm.permute(&mut [0, 0], 8).is_err());
+    assert!(perm.permute(&mut [0], 0).is_err());
+    // The low bit of the last byte is past the end of the data
+    assert_error(
+        perm.permute(&mut [0, 1], 15),
+        crate::error::encode_buffer_doesnt_match_bits(),
+    );
+    assert_error(
+        perm.unpermute(&mut [0, 1], 15),
+        crate::error::encode_buffer_doesnt_match_bits(),
+    );
+}
+
+#[test]
+fn test_siphash_2_4() {
+    use core::convert::TryInto;
+
+    // The reference test vectors from the SipHash paper: the key is the bytes
+    // 00..0f and the message for output `n` is the bytes 00..`n`-1
+    const EXPECTED: [u64; 64] = [
+        0x726fdb47dd0e0e31,
+        0x74f839c593dc67fd,
+        0x0d6c8009d9a94f5a,
+        0x85676696d7fb7e2d,
+        0xcf2794e0277187b7,
+        0x18765564cd99a68d,
+        0xcbc9466e58fee3ce,
+        0xab0200f58b01d137,
+        0x93f5f5799a932462,
+        0x9e0082df0ba9e4b0,
+        0x7a5dbbc594ddb9f3,
+        0xf4b32f46226bada7,
+        0x751e8fbc860ee5fb,
+        0x14ea5627c0843d90,
+        0xf723ca908e7af2ee,
+        0xa129ca6149be45e5,
+        0x3f2acc7f57c29bdb,
+        0x699ae9f52cbe4794,
+        0x4bc1b3f0968dd39c,
+        0xbb6dc91da77961bd,
+        0xbed65cf21aa2ee98,
+        0xd0f2cbb02e3b67c7,
+        0x93536795e3a33e88,
+        0xa80c038ccd5ccec8,
+        0xb8ad50c6f649af94,
+        0xbce192de8a85b8ea,
+        0x17d835b85bbb15f3,
+        0x2f2e6163076bcfad,
+        0xde4daaaca71dc9a5,
+        0xa6a2506687956571,
+        0xad87a3535c49ef28,
+        0x32d892fad841c342,
+        0x7127512f72f27cce,
+        0xa7f32346f95978e3,
+        0x12e0b01abb051238,
+        0x15e034d40fa197ae,
+        0x314dffbe0815a3b4,
+        0x027990f029623981,
+        0xcadcd4e59ef40c4d,
+        0x9abfd8766a33735c,
+        0x0e3ea96b5304a7d0,
+        0xad0c42d6fc585992,
+        0x187306c89bc215a9,
+        0xd4a60abcf3792b95,
+        0xf935451de4f21df2,
+        0xa9538f0419755787,
+        0xdb9acddff56ca510,
+        0xd06c98cd5c0975eb,
+        0xe612a3cb9ecba951,
+        0xc766e62cfcadaf96,
+        0xee64435a9752fe72,
+        0xa192d576b245165a,
+        0x0a8787bf8ecb74b2,
+        0x81b3e73d20b49b6f,
+        0x7fa8220ba3b2ecea,
+        0x245731c13ca42499,
+        0xb78dbfaf3a8d83bd,
+        0xea1ad565322a1a0b,
+        0x60e61c23a3795013,
+        0x6606d7e446282b93,
+        0x6ca4ecb15c5f91e1,
+        0x9f626da15c9625f3,
+        0xe51b38608ef25f57,
+        0x958a324ceb064572,
+    ];
+    let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+    let k0 = u64::from_le_bytes(key[..8].try_into().unwrap());
+    let k1 = u64::from_le_bytes(key[8..].try_into().unwrap());
+    let message: [u8; 64] = core::array::from_fn(|i| i as u8);
+    for (len, &expected) in EXPECTED.iter().enumerate() {
+        assert_eq!(
+            crate::permute::siphash_2_4(k0, k1, &message[..len]),
+            expected
+        );
+    }
+
+    // Data that is too long for any code is rejected before it's permuted
+    let perm = KeyedPermutation::new(&key);
+    assert_error(
+        perm.encode_chunk(&[0; 20], 0, 150),
+        crate::error::encode_buffer_too_big(),
+    );
+}
+
+#[cfg(feature = "auth")]
+#[test]
+fn test_code_authenticator() {
+    use crate::CodeAuthenticator;
+
+    const KEY: &[u8] = b"secret key";
+    const DATA: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xE0];
+    const BITS: u8 = 28;
+    const ECC: u8 = 4;
+
+    let auth = CodeAuthenticator::new(KEY, 40).unwrap();
+    let encoded = auth.encode_chunk(&DATA, ECC, BITS).unwrap();
+    // 28 data bits and 40 tag bits take 14 characters plus 4 ECC symbols
+    assert_eq!(encoded.encoded_len(ChunkFormat::Raw), 18);
+
+    let (decoded, corrected) = auth
+        .decode_chunk(encoded.pretty().as_str(), ECC, BITS)
+        .unwrap();
+    assert_eq!(decoded.as_bytes(), &DATA);
+    assert!(corrected.is_none());
+
+    // Typos are corrected before the tag is checked
+    let mut typo = [0u8; 18];
+    encoded.encode_into(ChunkFormat::Raw, &mut typo).unwrap();
+    typo[3] = b'!';
+    typo[10] = if typo[10] == b'y' { b'b' } else { b'y' };
+    let (decoded, corrected) = auth
+        .decode_chunk(core::str::from_utf8(&typo).unwrap(), ECC, BITS)
+        .unwrap();
+    assert_eq!(decoded.as_bytes(), &DATA);
+    assert_eq!(corrected.unwrap(), encoded);
+
+    // A valid code that wasn't created with the key fails authentication
+    let forged = CodeAuthenticator::new(b"other key", 40)
+        .unwrap()
+        .encode_chunk(&DATA, ECC, BITS)
+        .unwrap();
+    assert_input_error(
+        auth.decode_chunk(forged.raw().as_str(), ECC, BITS),
+        InputErrorKind::AuthenticationFailed,
+    );
+    // ...which can be told apart from a code that can't be corrected
+    assert_input_error(
+        auth.decode_chunk("!!!!!!!!!!!!!!!!!!", ECC, BITS),
+        InputErrorKind::Other,
+    );
+    // Changing only the last bit of a tag that doesn't fill its last byte
+    // fails authentication as well
+    let auth_12 = CodeAuthenticator::new(KEY, 12).unwrap();
+    let encoded_12 = auth_12.encode_chunk(&DATA, ECC, BITS).unwrap();
+    let (raw, _) = decode_chunk(encoded_12.raw().as_str(), ECC, BITS + 12).unwrap();
+    let (mut raw, len) = raw.into_inner();
+    raw[4] ^= 0x01;
+    let forged = encode_chunk(&raw[..len], ECC, BITS + 12).unwrap();
+    assert_input_error(
+        auth_12.decode_chunk(forged.raw().as_str(), ECC, BITS),
+        InputErrorKind::AuthenticationFailed,
+    );
+    // Unused bits of the data must be 0, like for encode_chunk()
+    assert!(auth
+        .encode_chunk(&[0xDE, 0xAD, 0xBE, 0xEF], ECC, BITS)
+        .is_err());
+
+    // Every tag size up to the limit works
+    for tag_bits in 1..=149u8 {
+        let auth = CodeAuthenticator::new(KEY, tag_bits).unwrap();
+        let encoded = auth.encode_chunk(&[0x80], 0, 1).unwrap();
+        let (decoded, _) = auth.decode_chunk(encoded.raw().as_str(), 0, 1).unwrap();
+        assert_eq!(decoded.as_bytes(), &[0x80]);
+    }
+
+    assert!(CodeAuthenticator::new(KEY, 0).is_err());
+    assert!(CodeAuthenticator::new(KEY, 150).is_err());
+    assert!(auth.encode_chunk(&[0; 14], 0, 111).is_err());
+    assert!(auth.encode_chunk(&DATA, ECC, 40).is_err());
+}
+
+#[cfg(feature = "encrypt")]
+#[test]
+fn test_payload_cipher() {
+    use crate::PayloadCipher;
+
+    const DATA: [u8; 5] = [0, 0, 0, 0, 42];
+    const BITS: u8 = 40;
+    const ECC: u8 = 4;
+
+    let cipher = PayloadCipher::new(b"deployment key");
+    let encoded = cipher.encode_chunk(&DATA, ECC, BITS).unwrap();
+    assert_eq!(
+        encoded.encoded_len(ChunkFormat::Raw),
+        encode_chunk(&DATA, ECC, BITS)
+            .unwrap()
+            .encoded_len(ChunkFormat::Raw)
+    );
+    assert_ne!(encoded, encode_chunk(&DATA, ECC, BITS).unwrap());
+
+    // The payload can't be read without the key
+    let (decoded, _) = decode_chunk(encoded.raw().as_str(), ECC, BITS).unwrap();
+    assert_ne!(decoded.as_bytes(), &DATA);
+
+    let mut typo = [0u8; 12];
+    encoded.encode_into(ChunkFormat::Raw, &mut typo).unwrap();
+    typo[0] = b'!';
+    let (decoded, corrected) = cipher
+        .decode_chunk(core::str::from_utf8(&typo).unwrap(), ECC, BITS)
+        .unwrap();
+    assert_eq!(decoded.as_bytes(), &DATA);
+    assert_eq!(corrected.unwrap(), encoded);
+
+    let other = PayloadCipher::new(b"other key");
+    let (decoded, _) = other
+        .decode_chunk(encoded.raw().as_str(), ECC, BITS)
+        .unwrap();
+    assert_ne!(decoded.as_bytes(), &DATA);
+
+    for bits in 1..=150u8 {
+        let len = (bits as usize).div_ceil(8);
+        let mut data = [0u8; 19];
+        data[0] = 0x80;
+        let mut encrypted = data;
+        cipher.encrypt(&mut encrypted[..len], bits).unwrap();
+        cipher.decrypt(&mut encrypted[..len], bits).unwrap();
+        assert_eq!(encrypted, data);
+    }
+}
+
+#[test]
+fn test_expiry() {
+    // Minutes since the start of 2024 in 16 bits, valid for 10 minutes
+    const EPOCH: u64 = 1_704_067_200;
+    const NOW: u64 = EPOCH + 1_000_000;
+    const DATA: [u8; 3] = [1, 2, 3];
+    const BITS: u8 = 24;
+    const ECC: u8 = 4;
+
+    let expiry = Expiry::new(EPOCH, 60, 16, 600).unwrap();
+    let encoded = expiry.encode_chunk(&DATA, ECC, BITS, NOW).unwrap();
+    // 24 data bits plus 16 time bits take 8 characters plus 4 ECC symbols
+    assert_eq!(encoded.encoded_len(ChunkFormat::Raw), 12);
+
+    for &now in [NOW, NOW + 1, NOW + 600].iter() {
+        let (decoded, corrected) = expiry
+            .decode_chunk(encoded.raw().as_str(), ECC, BITS, now)
+            .unwrap();
+        assert_eq!(decoded.as_bytes(), &DATA);
+        assert!(corrected.is_none());
+    }
+
+    // Too old, or from the future
+    for &now in [NOW + 700, NOW + 60 * 1000, NOW - 60].iter() {
+        assert_input_error(
+            expiry.decode_chunk(encoded.raw().as_str(), ECC, BITS, now),
+            InputErrorKind::Expired,
+        );
+    }
+
+    // The timestamp wraps around without affecting which codes are valid
+    let later = NOW + 60 * 65536;
+    let encoded = expiry.encode_chunk(&DATA, ECC, BITS, later).unwrap();
+    assert!(expiry
+        .decode_chunk(encoded.raw().as_str(), ECC, BITS, later + 300)
+        .is_ok());
+
+    // Typos are still corrected
+    let mut typo = [0u8; 12];
+    encoded.encode_into(ChunkFormat::Raw, &mut typo).unwrap();
+    typo[1] = b'!';
+    typo[9] = b'!';
+    let (decoded, corrected) = expiry
+        .decode_chunk(core::str::from_utf8(&typo).unwrap(), ECC, BITS, later)
+        .unwrap();
+    assert_eq!(decoded.as_bytes(), &DATA);
+    assert_eq!(corrected.unwrap(), encoded);
+
+    assert!(matches!(
+        expiry.encode_chunk(&DATA, ECC, BITS, EPOCH - 1),
+        Err(HumancodeError::UsageError(_))
+    ));
+    assert!(expiry.encode_chunk(&[0; 17], 0, 135, NOW).is_err());
+    // The low 4 bits of the last byte are past the end of the data
+    assert_error(
+        expiry.encode_chunk(&DATA, ECC, 20, NOW),
+        crate::error::encode_buffer_doesnt_match_bits(),
+    );
+    assert!(Expiry::new(EPOCH, 60, 0, 600).is_err());
+    assert!(Expiry::new(EPOCH, 0, 16, 600).is_err());
+    assert!(Expiry::new(EPOCH, 60, 3, 600).is_err());
+    assert!(Expiry::new(EPOCH, 1, 64, u64::MAX - 1).is_ok());
+}
+
+#[cfg(feature = "totp")]
+#[test]
+fn test_totp() {
+    use crate::Totp;
+
+    // The SHA-256 test vectors from RFC 6238
+    const SECRET: &[u8] = b"12345678901234567890123456789012";
+    const VECTORS: &[(u64, u32)] = &[
+        (59, 46119246),
+        (1111111109, 68084774),
+        (1111111111, 67062674),
+        (1234567890, 91819424),
+        (2000000000, 90698825),
+        (20000000000, 77737706),
+    ];
+    let totp = Totp::new(SECRET, 0, 30, 31, 4).unwrap();
+    for &(now, expected) in VECTORS.iter() {
+        assert_eq!(totp.value(now).unwrap() % 100_000_000, expected);
+    }
+
+    // 30 bits with 4 ECC symbols gives 10 character codes
+    let totp = Totp::new(SECRET, 0, 30, 30, 4).unwrap();
+    let now = 1_700_000_000;
+    let code = totp.generate(now).unwrap();
+    assert_eq!(code.encoded_len(ChunkFormat::Raw), 10);
+    assert_eq!(totp.verify(code.pretty().as_str(), now, 0).unwrap(), None);
+
+    // Typos are corrected before comparing
+    let mut typo = [0u8; 10];
+    code.encode_into(ChunkFormat::Raw, &mut typo).unwrap();
+    typo[2] = b'!';
+    typo[7] = if typo[7] == b'y' { b'b' } else { b'y' };
+    let typo = core::str::from_utf8(&typo).unwrap();
+    assert_eq!(totp.verify(typo, now, 0).unwrap(), Some(code));
+
+    // Adjacent time steps are only accepted within the skew
+    assert!(totp.verify(code.raw().as_str(), now + 30, 1).is_ok());
+    assert!(totp.verify(code.raw().as_str(), now - 30, 1).is_ok());
+    assert_input_error(
+        totp.verify(code.raw().as_str(), now + 30, 0),
+        InputErrorKind::AuthenticationFailed,
+    );
+    assert_input_error(
+        totp.verify(code.raw().as_str(), now + 90, 2),
+        InputErrorKind::AuthenticationFailed,
+    );
+
+    // A different secret gives different codes
+    let other = Totp::new(b"another secret", 0, 30, 30, 4).unwrap();
+    assert!(other.verify(code.raw().as_str(), now, 1).is_err());
+
+    assert!(Totp::new(SECRET, 0, 0, 30, 4).is_err());
+    assert!(Totp::new(SECRET, 0, 30, 32, 4).is_err());
+    assert!(Totp::new(SECRET, 0, 30, 0, 4).is_err());
+    assert!(Totp::new(SECRET, 0, 30, 30, 31).is_err());
+    assert!(Totp::new(SECRET, 100, 30, 30, 4)
+        .unwrap()
+        .generate(99)
+        .is_err());
+}
+
+#[cfg(all(feature = "alloc", feature = "rand"))]
+#[test]
+fn test_shamir() {
+    use crate::{combine_shares, share_code_bits, split_secret};
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    const SECRET: &[u8] = b"a 32 byte master key for testing";
+    const ECC: u8 = 8;
+
+    let mut rng = TestRng(11);
+    let shares = split_secret(SECRET, 3, 5, ECC, &mut rng).unwrap();
+    assert_eq!(shares.len(), 5);
+    // 8 ECC symbols leaves room for 10 bytes per code
+    assert_eq!(share_code_bits(ECC).unwrap(), 108);
+    for (i, share) in shares.iter().enumerate() {
+        assert_eq!(share.index() as usize, i + 1);
+        assert_eq!(share.codes().len(), 4);
+    }
+
+    let typed = |indexes: &[usize]| -> Vec<String> {
+        indexes
+            .iter()
+            .flat_map(|&i| shares[i].codes().iter().rev())
+            .map(|code| code.pretty().as_str().to_string())
+            .collect()
+    };
+
+    // Any 3 shares work, in any order
+    for indexes in [[0, 1, 2], [4, 2, 0], [1, 3, 4]].iter() {
+        assert_eq!(combine_shares(&typed(indexes), ECC).unwrap(), SECRET);
+    }
+    assert_eq!(
+        combine_shares(&typed(&[0, 1, 2, 3, 4]), ECC).unwrap(),
+        SECRET
+    );
+
+    // Typos are corrected
+    let mut codes = typed(&[3, 1, 0]);
+    codes[4].replace_range(0..2, "!!");
+    assert_eq!(combine_shares(&codes, ECC).unwrap(), SECRET);
+
+    // Not enough complete shares
+    let mut codes = typed(&[0, 1, 2]);
+    codes.pop();
+    assert!(matches!(
+        combine_shares(&codes, ECC),
+        Err(HumancodeError::InputError(_))
+    ));
+    assert!(combine_shares(&typed(&[0, 1]), ECC).is_err());
+    assert!(combine_shares::<&str>(&[], ECC).is_err());
+
+    // Shares of different secrets can't be mixed
+    let other = split_secret(SECRET, 2, 2, ECC, &mut rng).unwrap();
+    let mut codes = typed(&[0, 1]);
+    codes.extend(
+        other[1]
+            .codes()
+            .iter()
+            .map(|code| code.raw().as_str().to_string()),
+    );
+    assert!(combine_shares(&codes, ECC).is_err());
+    // Even if only the length of the secret differs
+    let shorter = split_secret(&SECRET[..31], 3, 3, ECC, &mut rng).unwrap();
+    let mut codes = typed(&[0, 1]);
+    codes.extend(
+        shorter[2]
+            .codes()
+            .iter()
+            .map(|code| code.raw().as_str().to_string()),
+    );
+    assert!(combine_shares(&codes, ECC).is_err());
+
+    // A 1 of 1 split still hides nothing, but works
+    let single = split_secret(&[42], 1, 1, 0, &mut rng).unwrap();
+    assert_eq!(
+        combine_shares(&[single[0].codes()[0].raw()], 0).unwrap(),
+        &[42]
+    );
+
+    assert!(split_secret(SECRET, 0, 5, ECC, &mut rng).is_err());
+    assert!(split_secret(SECRET, 6, 5, ECC, &mut rng).is_err());
+    assert!(split_secret(&[], 1, 1, ECC, &mut rng).is_err());
+    assert!(split_secret(&[0; 160], 1, 1, ECC, &mut rng).is_ok());
+    assert!(split_secret(&[0; 161], 1, 1, ECC, &mut rng).is_err());
+    assert!(split_secret(SECRET, 1, 1, 29, &mut rng).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_backup_sheet() {
+    use crate::{parse_backup_sheet, BackupSheet, LineStatus};
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    const DATA: &[u8] = b"a 36 byte long secret for the sheet!";
+    const ECC: u8 = 4;
+
+    let sheet = BackupSheet::new(DATA, 8, ECC).unwrap();
+    assert_eq!(sheet.lines().len(), 5);
+    let text = sheet.to_text();
+    assert_eq!(text.lines().count(), 5);
+    assert!(text.starts_with("01  "));
+    for (line, code) in text.lines().zip(sheet.lines()) {
+        assert_eq!(&line[4..], code.pretty().as_str());
+    }
+
+    // The whole Markdown document can be parsed back
+    let markdown = sheet.to_markdown("Recovery sheet");
+    assert!(markdown.starts_with("# Recovery sheet\n"));
+    let report = parse_backup_sheet(&markdown, ECC).unwrap();
+    assert_eq!(report.data(), Some(DATA));
+    assert_eq!(report.lines().len(), 5);
+    assert!(report
+        .lines()
+        .iter()
+        .all(|line| matches!(line.status(), LineStatus::Ok)));
+
+    // Typed lines in a different order, with other separators and a typo
+    let mut lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+    lines.swap(0, 3);
+    lines[1] = lines[1].replacen("  ", ": ", 1);
+    lines[2].replace_range(4..5, "!");
+    let report = parse_backup_sheet(&lines.join("\n"), ECC).unwrap();
+    assert_eq!(report.data(), Some(DATA));
+    assert_eq!(report.errors().count(), 0);
+    assert!(
+        matches!(report.lines()[2].status(), LineStatus::Corrected(code) if *code == sheet.lines()[2])
+    );
+
+    // A line with too many errors and a missing last line are both reported,
+    // and no data is returned
+    let mut lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+    lines.pop();
+    lines[1].replace_range(4..14, "!!!!-!!!!-");
+    let report = parse_backup_sheet(&lines.join("\n"), ECC).unwrap();
+    assert_eq!(report.data(), None);
+    let errors: Vec<usize> = report.errors().map(|line| line.line_number()).collect();
+    assert_eq!(errors, [2, 5]);
+    assert!(matches!(
+        report.lines()[1].status(),
+        LineStatus::Invalid(HumancodeError::InputError(_))
+    ));
+    assert!(matches!(report.lines()[4].status(), LineStatus::Missing));
+
+    // A line with the wrong line number is a conflict
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| line.replacen("03", "04", 1))
+        .collect();
+    let report = parse_backup_sheet(&lines.join("\n"), ECC).unwrap();
+    assert_eq!(report.data(), None);
+    assert!(matches!(
+        report.lines()[2].status(),
+        LineStatus::Conflicting
+    ));
+
+    // Empty data still makes a sheet with a single line
+    let empty = BackupSheet::new(&[], 8, ECC).unwrap();
+    assert_eq!(empty.lines().len(), 1);
+    let report = parse_backup_sheet(&empty.to_text(), ECC).unwrap();
+    assert_eq!(report.data(), Some(&[][..]));
+
+    assert!(BackupSheet::new(DATA, 0, ECC).is_err());
+    assert!(BackupSheet::new(&[0; 129], 1, ECC).is_err());
+    assert!(BackupSheet::new(DATA, 17, 8).is_err());
+    assert!(parse_backup_sheet(&text, 31).is_err());
+}
+
+#[cfg(feature = "render")]
+#[test]
+fn test_render() {
+    use crate::render_svg;
+    use qrcode::QrCode;
+
+    let code = encode_chunk(&[0x12, 0x34, 0x56, 0x78, 0x9a], 5, 40).unwrap();
+    let qr = QrCode::new(code.raw().as_str()).unwrap();
+    let width = 4 + qr.width() + 4 + code.pretty().as_str().len() * 6 - 1 + 4;
+    let height = qr.width() + 8;
+
+    let svg = render_svg(code);
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(&format!("viewBox=\"0 0 {} {}\"", width, height)));
+    assert!(svg.contains(&format!("<title>{}</title>", code.pretty())));
+    // The top row of the top left finder pattern
+    assert!(svg.contains("M4 4h7v1h-7z"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    #[cfg(feature = "png")]
+    {
+        use crate::render_png;
+        use qrcode::Color;
+
+        const SCALE: usize = 3;
+        let png = render_png(code, SCALE as u32).unwrap();
+        let decoder = png::Decoder::new(&png[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let mut image = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut image).unwrap();
+        assert_eq!(info.width as usize, width * SCALE);
+        assert_eq!(info.height as usize, height * SCALE);
+
+        // The QR code in the image matches the raw form of the code
+        let pixel = |x: usize, y: usize| image[(y * SCALE + 1) * width * SCALE + x * SCALE + 1];
+        for (i, color) in qr.to_colors().into_iter().enumerate() {
+            let expected = if color == Color::Dark { 0 } else { 0xff };
+            assert_eq!(pixel(4 + i % qr.width(), 4 + i / qr.width()), expected);
+        }
+
+        assert!(render_png(code, 0).is_err());
+        assert!(render_png(code, 65).is_err());
+    }
+}
+
+#[test]
+fn test_numeric() {
+    use crate::decode_chunk_numeric;
+    use crate::decoder::CHUNK_DECODER_4;
+
+    const DATA: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x42];
+    const ECC: u8 = 4;
+
+    let code = encode_chunk(DATA, ECC, 40).unwrap();
+    let numeric = code.numeric();
+    assert_eq!(numeric.as_str().len(), 2 * code.raw().as_str().len());
+    assert!(numeric.as_str().bytes().all(|x| x.is_ascii_digit()));
+    // "y" is the first z-base-32 character and "9" is the last
+    assert_eq!(
+        encode_chunk(&[0x00], 0, 5).unwrap().numeric().as_str(),
+        "00"
+    );
+    assert_eq!(
+        encode_chunk(&[0xf8], 0, 5).unwrap().numeric().as_str(),
+        "31"
+    );
+
+    let (decoded, corrected) = decode_chunk_numeric(numeric.as_str(), ECC, 40).unwrap();
+    assert_eq!(decoded.as_bytes(), DATA);
+    assert!(corrected.is_none());
+
+    // Separators are ignored
+    let mut grouped = FmtBuf::new();
+    for (i, group) in numeric.as_str().as_bytes().chunks(4).enumerate() {
+        if i > 0 {
+            grouped.write_str(" ").unwrap();
+        }
+        grouped
+            .write_str(core::str::from_utf8(group).unwrap())
+            .unwrap();
+    }
+    assert_eq!(
+        CHUNK_DECODER_4
+            .decode_chunk_numeric(grouped.as_str(), 40)
+            .unwrap()
+            .0
+            .as_bytes(),
+        DATA
+    );
+
+    // Wrong digits, unknown digits, and pairs above 31 are all corrected
+    let mut buf = [0u8; 64];
+    let typed = &mut buf[..numeric.as_str().len()];
+    typed.copy_from_slice(numeric.as_str().as_bytes());
+    typed[0] = if typed[0] == b'1' { b'2' } else { b'1' };
+    typed[5] = b'*';
+    typed[8..10].copy_from_slice(b"99");
+    let typed = core::str::from_utf8(typed).unwrap();
+    let (decoded, corrected) = decode_chunk_numeric(typed, ECC, 40).unwrap();
+    assert_eq!(decoded.as_bytes(), DATA);
+    assert_eq!(corrected.unwrap().numeric(), numeric);
+
+    // A missing digit changes the length
+    assert!(decode_chunk_numeric(&numeric.as_str()[1..], ECC, 40).is_err());
+    let ones = core::str::from_utf8(&[b'1'; 64]).unwrap();
+    assert!(decode_chunk_numeric(ones, ECC, 40).is_err());
+}
+
+#[test]
+fn test_check_digit() {
+    use crate::CheckDigit;
+
+    const DATA: &[u8] = &[0xa5, 0x3c];
+    const BITS: u8 = 16;
+
+    for scheme in [CheckDigit::Damm, CheckDigit::Verhoeff].iter() {
+        let code = scheme.encode_chunk(DATA, BITS).unwrap();
+        let raw = code.raw();
+        // One character more than the code without any error correction
+        assert_eq!(
+            raw.as_str().len(),
+            encode_chunk(DATA, 0, BITS).unwrap().raw().as_str().len() + 1
+        );
+        assert!(raw
+            .as_str()
+            .starts_with(encode_chunk(DATA, 0, BITS).unwrap().raw().as_str()));
+
+        let (decoded, corrected) = scheme.decode_chunk(code.pretty().as_str(), BITS).unwrap();
+        assert_eq!(decoded.as_bytes(), DATA);
+        assert!(corrected.is_none());
+
+        // Every single character error is detected
+        let alphabet = "ybndrfg8ejkmcpqxot1uwisza345h769".as_bytes();
+        for i in 0..raw.as_str().len() {
+            for &c in alphabet.iter() {
+                let mut buf = [0u8; 32];
+                let typed = &mut buf[..raw.as_str().len()];
+                typed.copy_from_slice(raw.as_str().as_bytes());
+                if typed[i] == c {
+                    continue;
+                }
+                typed[i] = c;
+                assert!(matches!(
+                    scheme.decode_chunk(core::str::from_utf8(typed).unwrap(), BITS),
+                    Err(HumancodeError::InputError(_)) | Err(HumancodeError::UsageError(_))
+                ));
+            }
+        }
+    }
+
+    // Every adjacent transposition is detected, for every pair of characters
+    let alphabet = "ybndrfg8ejkmcpqxot1uwisza345h769".as_bytes();
+    for scheme in [CheckDigit::Damm, CheckDigit::Verhoeff].iter() {
+        for &a in alphabet.iter() {
+            for &b in alphabet.iter().filter(|&&b| b != a) {
+                let data = [a, b, b'y'];
+                let data = core::str::from_utf8(&data).unwrap();
+                let (decoded, _) = decode_chunk(data, 0, 15).unwrap();
+                let code = scheme.encode_chunk(decoded.as_bytes(), 15).unwrap();
+                // 15 bits take 3 characters, plus the check character
+                let mut swapped = [0u8; 4];
+                swapped.copy_from_slice(code.raw().as_str().as_bytes());
+                swapped.swap(0, 1);
+                assert!(scheme
+                    .decode_chunk(core::str::from_utf8(&swapped).unwrap(), 15)
+                    .is_err());
+                swapped.swap(0, 1);
+                swapped.swap(2, 3);
+                if swapped[2] != swapped[3] {
+                    assert!(scheme
+                        .decode_chunk(core::str::from_utf8(&swapped).unwrap(), 15)
+                        .is_err());
+                }
+            }
+        }
+    }
+
+    // Even the longest data leaves room for the check character
+    let code = CheckDigit::Damm.encode_chunk(&[0; 19], 150).unwrap();
+    assert_eq!(code.raw().as_str().len(), 31);
+    assert!(CheckDigit::Verhoeff.decode_chunk("y", 1).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_interleaved() {
+    use crate::{decode_interleaved, encode_interleaved};
+    use alloc::string::ToString;
+
+    const DATA: &[u8] = b"a 32 byte long key for a code!!!";
+    const BITS: u16 = 256;
+    const ECC: u8 = 4;
+    const DEPTH: u8 = 4;
+
+    let code = encode_interleaved(DATA, BITS, ECC, DEPTH).unwrap();
+    // 4 codewords of 64 bits - 13 data and 4 error correcting symbols each
+    assert_eq!(code.raw().len(), 4 * 17);
+    assert_eq!(code.pretty().len(), 68 + 16);
+    assert_eq!(code.to_string(), code.pretty());
+
+    let (decoded, corrected) = decode_interleaved(&code.pretty(), BITS, ECC, DEPTH).unwrap();
+    assert_eq!(decoded, DATA);
+    assert!(corrected.is_none());
+
+    // A smudged group of 8 characters only puts 2 errors in each codeword
+    let mut typed = code.raw().to_string();
+    typed.replace_range(20..28, "yyyyyyyy");
+    let (decoded, corrected) = decode_interleaved(&typed, BITS, ECC, DEPTH).unwrap();
+    assert_eq!(decoded, DATA);
+    assert_eq!(corrected, Some(code.clone()));
+    // Unreadable characters are cheaper to correct - up to 16 in a row, but
+    // 17 puts 5 erasures in one codeword
+    let mut typed = code.raw().to_string();
+    typed.replace_range(20..36, &"!".repeat(16));
+    assert!(decode_interleaved(&typed, BITS, ECC, DEPTH).is_ok());
+    typed.replace_range(36..37, "!");
+    assert!(decode_interleaved(&typed, BITS, ECC, DEPTH).is_err());
+
+    // The data doesn't have to split evenly
+    let mut data = DATA[..20].to_vec();
+    data[19] &= 0xe0;
+    let code = encode_interleaved(&data, 155, ECC, 2).unwrap();
+    let (decoded, _) = decode_interleaved(code.raw(), 155, ECC, 2).unwrap();
+    assert_eq!(decoded, data);
+    assert!(encode_interleaved(&[0xff; 20], 155, ECC, 2).is_err());
+
+    assert!(decode_interleaved(&code.raw()[1..], 155, ECC, 2).is_err());
+    assert!(encode_interleaved(DATA, BITS, ECC, 0).is_err());
+    assert!(encode_interleaved(DATA, BITS, ECC, 9).is_err());
+    assert!(encode_interleaved(DATA, BITS, ECC, 1).is_err());
+    assert!(encode_interleaved(DATA, BITS, 20, DEPTH).is_err());
+}
+
+// Corrupt codes with more errors and erasures than `ecc` symbols can correct
+// and check that `decode` gives the same result as the built in decoder -
+// either both reject the code, or both correct it to the same nearby code.
+fn check_over_capacity<F>(ecc: u8, decode: F)
+where
+    F: Fn(&str) -> Result<(crate::DecodedChunk, Option<EncodedChunk>), HumancodeError>,
+{
+    const BITS: u8 = 40;
+    const ALPHABET: &[u8] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+    let mut rng = TestRng(0x9e37_79b9_7f4a_7c15 ^ ecc as u64);
+    for _ in 0..200 {
+        let mut data = [0u8; 5];
+        for x in data.iter_mut() {
+            *x = rng.next() as u8;
+        }
+        let code = encode_chunk(&data, ecc, BITS).unwrap();
+        let raw = code.raw();
+        let raw = raw.as_str().as_bytes();
+        let mut buf = [0u8; 31];
+        let typed = &mut buf[..raw.len()];
+        typed.copy_from_slice(raw);
+
+        let (errors, erasures) = loop {
+            let errors = rng.below(raw.len() + 1);
+            let erasures = rng.below(raw.len() + 1 - errors);
+            if 2 * errors + erasures > ecc as usize {
+                break (errors, erasures);
+            }
+        };
+        let mut positions = [0usize; 31];
+        for (i, x) in positions.iter_mut().enumerate() {
+            *x = i;
+        }
+        let positions = &mut positions[..raw.len()];
+        for i in (1..positions.len()).rev() {
+            positions.swap(i, rng.below(i + 1));
+        }
+        for &pos in positions[..errors].iter() {
+            // Always move to a different character
+            let c = ALPHABET.iter().position(|&x| x == typed[pos]).unwrap();
+            typed[pos] = ALPHABET[(c + 1 + rng.below(31)) % 32];
+        }
+        for &pos in positions[errors..errors + erasures].iter() {
+            typed[pos] = b'!';
+        }
+
+        let typed = core::str::from_utf8(typed).unwrap();
+        match (decode_chunk(typed, ecc, BITS), decode(typed)) {
+            (Ok((expected, expected_corrected)), Ok((decoded, corrected))) => {
+                assert_eq!(decoded.as_bytes(), expected.as_bytes(), "{}", typed);
+                assert_eq!(corrected, expected_corrected, "{}", typed);
+            }
+            (Err(_), Err(_)) => {}
+            (expected, decoded) => panic!(
+                "{}: the built in decoder returned {}, but got {}",
+                typed,
+                if expected.is_ok() {
+                    "a code"
+                } else {
+                    "an error"
+                },
+                if decoded.is_ok() {
+                    "a code"
+                } else {
+                    "an error"
+                },
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_custom_rs_params() {
+    use crate::decoder::ChunkDecoder;
+    use crate::encoder::ChunkEncoder;
+    use crate::RsParams;
+
+    const DATA: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x42];
+
+    // The default parameters produce the same codes as the built in tables
+    for ecc in 0..=22 {
+        let encoder = ChunkEncoder::with_params(RsParams::DEFAULT, ecc).unwrap();
+        let code = encoder.encode_chunk(DATA, 40).unwrap();
+        assert_eq!(code, encode_chunk(DATA, ecc, 40).unwrap());
+    }
+    assert_eq!(
+        ChunkEncoder::with_params(RsParams::DEFAULT, 4)
+            .unwrap()
+            .encode_chunk(DATA, 40)
+            .unwrap()
+            .raw(),
+        "54s5754nt7oq"
+    );
+
+    // A code with x^5 + x^3 + 1, the generator 3, and a first consecutive root
+    // of 1 - the expected code was computed the same way as the Python
+    // reedsolo package does
+    let params = RsParams::new(0x29, 3, 1).unwrap();
+    assert_eq!(params.primitive_poly(), 0x29);
+    assert_eq!(params.generator(), 3);
+    assert_eq!(params.fcr(), 1);
+    let encoder = ChunkEncoder::with_params(params, 4).unwrap();
+    let decoder = ChunkDecoder::with_params(params, 4).unwrap();
+    let code = encoder.encode_chunk(DATA, 40).unwrap();
+    assert_eq!(code.raw(), "54s5754nppce");
+    assert_eq!(
+        decoder
+            .decode_chunk("54s5754nppce", 40)
+            .unwrap()
+            .0
+            .as_bytes(),
+        DATA
+    );
+    // ...which isn't a valid code with the default parameters
+    assert!(decode_chunk("54s5754nppce", 4, 40).is_err());
+
+    // Errors and erasures are corrected for other parameters as well
+    for &(poly, generator, fcr) in [(0x25, 2, 0), (0x29, 3, 1), (0x2f, 7, 5), (0x3d, 30, 30)].iter()
+    {
+        let params = RsParams::new(poly, generator, fcr).unwrap();
+        let encoder = ChunkEncoder::with_params(params, 6).unwrap();
+        let decoder = ChunkDecoder::with_params(params, 6).unwrap();
+        let code = encoder.encode_chunk(DATA, 40).unwrap();
+        // 40 bits take 8 characters, plus 6 error correcting symbols
+        let mut raw = [0u8; 14];
+        raw.copy_from_slice(code.raw().as_str().as_bytes());
+
+        let mut typed = raw;
+        typed[2..4].copy_from_slice(if &raw[2..4] == b"yy" { b"99" } else { b"yy" });
+        let (decoded, corrected) = decoder
+            .decode_chunk(core::str::from_utf8(&typed).unwrap(), 40)
+            .unwrap();
+        assert_eq!(decoded.as_bytes(), DATA);
+        assert_eq!(corrected, Some(code));
+
+        let mut typed = raw;
+        typed[..3].copy_from_slice(b"!!!");
+        typed[11..].copy_from_slice(b"!!!");
+        assert_eq!(
+            decoder
+                .decode_chunk(core::str::from_utf8(&typed).unwrap(), 40)
+                .unwrap()
+                .0
+                .as_bytes(),
+            DATA
+        );
+        typed[3] = b'!';
+        assert!(decoder
+            .decode_chunk(core::str::from_utf8(&typed).unwrap(), 40)
+            .is_err());
+    }
+
+    // Codes with more errors than can be corrected are rejected, just like
+    // with the built in tables
+    assert_eq!(encode_chunk(DATA, 1, 40).unwrap().raw(), "54s5754n1");
+    let decoder = ChunkDecoder::with_params(RsParams::DEFAULT, 1).unwrap();
+    assert!(decoder.decode_chunk("m4s5754n1", 40).is_err());
+    assert!(decoder.decode_chunk("!!!!!!!!!", 40).is_err());
+    for ecc in 1..=8 {
+        let decoder = ChunkDecoder::with_params(RsParams::DEFAULT, ecc).unwrap();
+        check_over_capacity(ecc, |typed| decoder.decode_chunk(typed, 40));
+    }
+
+    // The polynomial has to be irreducible and of degree 5
+    assert!(RsParams::new(0x25, 1, 0).is_err());
+    assert!(RsParams::new(0x25, 0, 0).is_err());
+    assert!(RsParams::new(0x25, 32, 0).is_err());
+    assert!(RsParams::new(0x25, 2, 31).is_err());
+    assert!(RsParams::new(0x13, 2, 0).is_err());
+    assert!(RsParams::new(0x21, 2, 0).is_err());
+    assert!(RsParams::new(0x3f, 2, 0).is_err());
+
+    // ecc can be anything in [0,30], as long as there is room left for the
+    // data
+    for &(ecc, bits) in [(0, 150), (30, 5)].iter() {
+        let len = (bits as usize).div_ceil(8);
+        let data = [0x80u8; 19];
+        let encoder = ChunkEncoder::with_params(RsParams::DEFAULT, ecc).unwrap();
+        let decoder = ChunkDecoder::with_params(RsParams::DEFAULT, ecc).unwrap();
+        let code = encoder.encode_chunk(&data[..len], bits).unwrap();
+        assert_eq!(code, encode_chunk(&data[..len], ecc, bits).unwrap());
+        let (decoded, corrected) = decoder.decode_chunk(code.raw().as_str(), bits).unwrap();
+        assert_eq!(decoded.as_bytes(), &data[..len]);
+        assert!(corrected.is_none());
+    }
+    let encoder = ChunkEncoder::with_params(RsParams::DEFAULT, 30).unwrap();
+    assert!(encoder.encode_chunk(&[0x80], 6).is_err());
+    assert_error(
+        ChunkEncoder::with_params(RsParams::DEFAULT, 31),
+        crate::error::invalid_ecc_len(),
+    );
+    assert_error(
+        ChunkDecoder::with_params(RsParams::DEFAULT, 31),
+        crate::error::invalid_ecc_len(),
+    );
+}
+
+#[cfg(feature = "runtime-tables")]
+#[test]
+fn test_runtime_tables() {
+    use crate::{RsParams, RuntimeChunkDecoder, RuntimeChunkEncoder, RS_TABLES_LEN};
+
+    const DATA: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x42];
+
+    // The buffer is reused to make sure that old contents don't matter
+    let mut buf = [0xffu8; RS_TABLES_LEN];
+    for ecc in 0..=22 {
+        let encoder = RuntimeChunkEncoder::new(&mut buf, ecc).unwrap();
+        let code = encoder.encode_chunk(DATA, 40).unwrap();
+        assert_eq!(code, encode_chunk(DATA, ecc, 40).unwrap());
+        let (decoded, corrected) = encoder
+            .decoder()
+            .decode_chunk(code.raw().as_str(), 40)
+            .unwrap();
+        assert_eq!(decoded.as_bytes(), DATA);
+        assert!(corrected.is_none());
+    }
+
+    let mut buf = [0u8; RS_TABLES_LEN];
+    let decoder = RuntimeChunkDecoder::new(&mut buf, 4).unwrap();
+    let (decoded, corrected) = decoder.decode_chunk("54s5-yy4n-t7oq", 40).unwrap();
+    assert_eq!(decoded.as_bytes(), DATA);
+    assert_eq!(corrected.unwrap().raw(), "54s5754nt7oq");
+    assert!(decoder.decode_chunk("y4sy-yy4n-t7oq", 40).is_err());
+
+    // Codes with more errors than can be corrected are rejected, just like
+    // with the built in tables
+    for ecc in 1..=8 {
+        let mut buf = [0u8; RS_TABLES_LEN];
+        let decoder = RuntimeChunkDecoder::new(&mut buf, ecc).unwrap();
+        check_over_capacity(ecc, |typed| decoder.decode_chunk(typed, 40));
+    }
+
+    let mut buf = [0u8; RS_TABLES_LEN];
+    let encoder =
+        RuntimeChunkEncoder::with_params(&mut buf, RsParams::new(0x29, 3, 1).unwrap(), 4).unwrap();
+    assert_eq!(
+        encoder.encode_chunk(DATA, 40).unwrap().raw(),
+        "54s5754nppce"
+    );
+
+    let mut buf = [0u8; RS_TABLES_LEN];
+    assert!(RuntimeChunkEncoder::new(&mut buf, 31).is_err());
+    assert!(RuntimeChunkDecoder::new(&mut buf, 31).is_err());
+}
+
+// Build the size_builtin, size_builtin_all, and size_runtime_tables
+// examples, optimized for size, and compare them. All of the binaries include
+// the standard library, so only the differences between them are meaningful.
+// Run with:
+// cargo test --features runtime-tables -- --ignored --nocapture
+#[cfg(all(feature = "runtime-tables", feature = "std"))]
+#[test]
+#[ignore = "builds the examples in release mode, which is slow"]
+fn test_runtime_tables_binary_size() {
+    use std::path::Path;
+    use std::process::Command;
+
+    // Computing the tables at runtime replaces the precomputed tables for a
+    // single ECC level with the code to compute them, which shouldn't cost
+    // more than this many extra bytes
+    const MAX_EXTRA_BYTES: i64 = 512;
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => manifest_dir.join(dir),
+        None => manifest_dir.join("target"),
+    }
+    .join("size-test");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args([
+            "build",
+            "--release",
+            "--examples",
+            "--features",
+            "runtime-tables",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env("CARGO_PROFILE_RELEASE_OPT_LEVEL", "s")
+        .env("CARGO_PROFILE_RELEASE_LTO", "true")
+        .env("CARGO_PROFILE_RELEASE_CODEGEN_UNITS", "1")
+        .env("CARGO_PROFILE_RELEASE_STRIP", "true")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let size = |name: &str| {
+        let path = target_dir.join("release").join("examples").join(format!(
+            "{}{}",
+            name,
+            std::env::consts::EXE_SUFFIX
+        ));
+        std::fs::metadata(path).unwrap().len() as i64
+    };
+    let single = size("size_builtin");
+    let all = size("size_builtin_all");
+    let runtime = size("size_runtime_tables");
+    println!(
+        "runtime tables: {} bytes, builtin tables for one level: {} bytes ({:+}), builtin tables for every level: {} bytes ({:+})",
+        runtime,
+        single,
+        runtime - single,
+        all,
+        runtime - all
+    );
+    assert!(runtime <= single + MAX_EXTRA_BYTES);
+    assert!(runtime < all);
+}
+
+#[test]
+fn test_decode_chunk_profiles() {
+    use crate::decode_chunk_profiles;
+
+    const DATA: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x42];
+    const PROFILES: &[(u8, u8)] = &[(40, 4), (40, 6)];
+
+    // Codes from either profile are decoded with the right one
+    for &(bits, ecc) in PROFILES.iter() {
+        let code = encode_chunk(DATA, ecc, bits).unwrap();
+        let found = decode_chunk_profiles(code.pretty().as_str(), PROFILES).unwrap();
+        assert_eq!((found.bits(), found.ecc()), (bits, ecc));
+        assert_eq!(found.decoded().as_bytes(), DATA);
+        assert!(found.corrected().is_none());
+
+        let mut buf = [0u8; 31];
+        let typed = &mut buf[..code.raw().as_str().len()];
+        typed.copy_from_slice(code.raw().as_str().as_bytes());
+        typed[..2].copy_from_slice(b"yy");
+        let typed = core::str::from_utf8(typed).unwrap();
+        let found = decode_chunk_profiles(typed, PROFILES).unwrap();
+        assert_eq!((found.bits(), found.ecc()), (bits, ecc));
+        let (decoded, corrected) = found.into_parts();
+        assert_eq!(decoded.as_bytes(), DATA);
+        assert_eq!(corrected, Some(code));
+    }
+
+    // Profiles of the same length that can't decode the code are skipped
+    let code = encode_chunk(DATA, 4, 40).unwrap();
+    let found = decode_chunk_profiles(code.raw().as_str(), &[(35, 5), (40, 4)]).unwrap();
+    assert_eq!((found.bits(), found.ecc()), (40, 4));
+    assert!(found.corrected().is_none());
+
+    // A profile that doesn't need any corrections is preferred. Changing 2
+    // characters by the same amount leaves a code with 1 error correcting
+    // symbol valid, while the same code is corrected with 4.
+    const ALPHABET: &[u8] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+    let flip = |c: u8| ALPHABET[ALPHABET.iter().position(|&x| x == c).unwrap() ^ 1];
+    // 40 bits take 8 characters, plus 4 error correcting symbols
+    let mut typed = [0u8; 12];
+    typed.copy_from_slice(code.raw().as_str().as_bytes());
+    typed[0] = flip(typed[0]);
+    typed[1] = flip(typed[1]);
+    let typed = core::str::from_utf8(&typed).unwrap();
+    assert!(decode_chunk(typed, 4, 40).unwrap().1.is_some());
+    let found = decode_chunk_profiles(typed, &[(40, 4), (55, 1)]).unwrap();
+    assert_eq!((found.bits(), found.ecc()), (55, 1));
+    assert!(found.corrected().is_none());
+
+    // A valid code is also valid with fewer error correcting symbols
+    assert!(decode_chunk_profiles(code.raw().as_str(), &[(40, 4), (45, 3)]).is_err());
+    // ...but duplicate profiles aren't ambiguous
+    assert!(decode_chunk_profiles(code.raw().as_str(), &[(40, 4), (40, 4)]).is_ok());
+
+    assert!(decode_chunk_profiles("54s5754nt7o", PROFILES).is_err());
+    assert!(decode_chunk_profiles("yyyyyyyyt7oq", PROFILES).is_err());
+    assert!(decode_chunk_profiles("54s5754nt7oq", &[]).is_err());
+    assert!(decode_chunk_profiles("54s5754nt7oq", &[(40, 31)]).is_err());
+    assert!(decode_chunk_profiles("54s5754nt7oq", &[(0, 4)]).is_err());
+}
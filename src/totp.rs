@@ -0,0 +1,153 @@
+use crate::decode_chunk::decode_chunk;
+use crate::encode_chunk::{encode_chunk, EncodedChunk};
+use crate::error::{
+    authentication_failed, invalid_bits, invalid_time_step, time_before_epoch, HumancodeError,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// `Totp` creates time-based one time codes, like the 6 digit codes from an
+/// authenticator app, but as z-base-32 codes with error correction.
+///
+/// The value of a code is calculated as described in RFC 6238 using
+/// HMAC-SHA256: the number of `step` second periods since `epoch` is
+/// hashed with the shared secret and the result is truncated to 31 bits as
+/// in RFC 4226. The lowest `bits` bits of that are encoded with `ecc` error
+/// correcting symbols. Times are given in seconds - for example, seconds
+/// since the Unix epoch.
+///
+/// When verifying, typos are corrected before the value is compared, so a
+/// small mistake doesn't cause a valid code to be rejected.
+#[derive(Clone)]
+pub struct Totp {
+    mac: Hmac<Sha256>,
+    epoch: u64,
+    step: u64,
+    bits: u8,
+    ecc: u8,
+}
+
+impl Totp {
+    /// Create a new `Totp` from a shared secret.
+    ///
+    /// `step` must be at least 1 second - RFC 6238 recommends 30 seconds.
+    /// `bits` must be in the range [1,31]. `ecc` is the number of error
+    /// correcting symbols to add to each code.
+    pub fn new(
+        secret: &[u8],
+        epoch: u64,
+        step: u64,
+        bits: u8,
+        ecc: u8,
+    ) -> Result<Totp, HumancodeError> {
+        if step == 0 {
+            return Err(invalid_time_step());
+        }
+        if bits == 0 || bits > 31 {
+            return Err(invalid_bits());
+        }
+        // Make sure that codes of this size can be encoded
+        encode_chunk(&[0u8; 4][..(bits as usize).div_ceil(8)], ecc, bits)?;
+        Ok(Totp {
+            mac: Hmac::new_from_slice(secret).expect("HMAC accepts keys of any length"),
+            epoch,
+            step,
+            bits,
+            ecc,
+        })
+    }
+
+    /// Get the value of the code for the time `now`.
+    ///
+    /// With `bits` set to 31, this is the value from RFC 6238 before it is
+    /// reduced to a number of decimal digits.
+    pub fn value(&self, now: u64) -> Result<u32, HumancodeError> {
+        Ok(self.value_for_counter(self.counter(now)?))
+    }
+
+    /// Create the code for the time `now`.
+    pub fn generate(&self, now: u64) -> Result<EncodedChunk, HumancodeError> {
+        self.encode_value(self.value(now)?)
+    }
+
+    /// Check a code that the user typed.
+    ///
+    /// The code is corrected and then compared with the codes for the time
+    /// step of `now` and for `skew` time steps before and after it, to allow
+    /// for clock differences and for the time it takes to type the code.
+    ///
+    /// On success, the corrected code is returned if there was an error in
+    /// the input. If the code can be decoded but doesn't match, an
+    /// [`InputError`](crate::HumancodeError::InputError) with the
+    /// [`AuthenticationFailed`](crate::InputErrorKind::AuthenticationFailed)
+    /// kind is returned.
+    pub fn verify(
+        &self,
+        input: &str,
+        now: u64,
+        skew: u8,
+    ) -> Result<Option<EncodedChunk>, HumancodeError> {
+        let counter = self.counter(now)?;
+        let (decoded, corrected) = decode_chunk(input, self.ecc, self.bits)?;
+        let mut value = [0u8; 4];
+        value[..decoded.len()].copy_from_slice(&decoded);
+        let value = u32::from_be_bytes(value) >> (32 - self.bits);
+
+        // Check every step in the window, so that the time taken doesn't
+        // depend on which step matched.
+        let mut matched = false;
+        let first = counter.saturating_sub(skew as u64);
+        let last = counter.saturating_add(skew as u64);
+        for c in first..=last {
+            matched |= self.value_for_counter(c) == value;
+        }
+        if !matched {
+            return Err(authentication_failed());
+        }
+        Ok(corrected)
+    }
+
+    fn counter(&self, now: u64) -> Result<u64, HumancodeError> {
+        match now.checked_sub(self.epoch) {
+            Some(elapsed) => Ok(elapsed / self.step),
+            None => Err(time_before_epoch()),
+        }
+    }
+
+    fn value_for_counter(&self, counter: u64) -> u32 {
+        let mut mac = self.mac.clone();
+        mac.update(&counter.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        // Dynamic truncation from RFC 4226
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let truncated = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        truncated & ((1u32 << self.bits) - 1)
+    }
+
+    fn encode_value(&self, value: u32) -> Result<EncodedChunk, HumancodeError> {
+        let data = (value << (32 - self.bits)).to_be_bytes();
+        encode_chunk(
+            &data[..(self.bits as usize).div_ceil(8)],
+            self.ecc,
+            self.bits,
+        )
+    }
+}
+
+impl core::fmt::Debug for Totp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Don't leak the secret into logs
+        f.debug_struct("Totp")
+            .field("epoch", &self.epoch)
+            .field("step", &self.step)
+            .field("bits", &self.bits)
+            .field("ecc", &self.ecc)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn humancode(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_humancode"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_decode() {
+    let output = humancode(&["encode", "--bits", "32", "--ecc", "4", "deadbeef"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let code = stdout(&output).trim().to_string();
+
+    let output = humancode(&["decode", "--bits", "32", "--ecc", "4", &code], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), format!("deadbeef\t{}\tok\n", code));
+
+    // A typo is corrected, and the code is read from stdin
+    let mut typo = code.clone();
+    typo.replace_range(0..1, if typo.starts_with('y') { "b" } else { "y" });
+    let output = humancode(
+        &[
+            "decode", "--bits", "32", "--ecc", "4", "--output", "decimal",
+        ],
+        &typo,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        format!("3735928559\t{}\tcorrected\n", code)
+    );
+}
+
+#[test]
+fn test_decode_uncorrectable() {
+    let output = humancode(&["encode", "--bits", "32", "--ecc", "4", "deadbeef"], "");
+    let code = stdout(&output).trim().to_string();
+
+    // 5 unreadable characters is more than 4 ECC symbols can correct
+    let mut broken = code.clone();
+    broken.replace_range(0..5, "!!!!!");
+    let output = humancode(&["decode", "--bits", "32", "--ecc", "4", &broken], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert!(std::str::from_utf8(&output.stderr)
+        .unwrap()
+        .starts_with("Error: "));
+
+    // In batch mode, the other lines are still decoded
+    let output = humancode(
+        &["decode", "--bits", "32", "--ecc", "4", "--batch"],
+        &format!("{}\n{}\n", broken, code),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let lines: Vec<&str> = stdout(&output).lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("\t\terror: "));
+    assert_eq!(lines[1], format!("deadbeef\t{}\tok", code));
+
+    // Usage errors have their own exit code
+    let output = humancode(&["decode", "--bits", "32", "--ecc", "31", &code], "");
+    assert_eq!(output.status.code(), Some(2));
+}